@@ -0,0 +1,52 @@
+//! Discord custom status, set alongside Slack when a Discord token is configured.
+
+use anyhow::Result;
+use chrono::DateTime;
+
+use crate::{Config, Zone, log_request, print_dry_run_method, read_response, unicode_emoji, with_retries};
+
+const DISCORD_SETTINGS_URL: &str = "https://discord.com/api/v9/users/@me/settings";
+
+/// Sets the Discord custom status, ending at `expires`. An emoji Discord
+/// can't show is left off rather than failing the whole status.
+pub fn set_discord_status(
+    token: &str,
+    text: &str,
+    slack_emoji: &str,
+    expires: Option<DateTime<Zone>>,
+    config: &Config,
+    dry_run: bool,
+) -> Result<()> {
+    let body = serde_json::json!({
+        "custom_status": {
+            "text": text,
+            "emoji_name": unicode_emoji(slack_emoji),
+            "expires_at": expires.map(|dt| dt.to_utc().format("%Y-%m-%dT%H:%M:%SZ").to_string()),
+        }
+    });
+    discord_settings(token, &body, config.retries(), dry_run)
+}
+
+pub fn clear_discord_status(token: &str, config: &Config, dry_run: bool) -> Result<()> {
+    let body = serde_json::json!({ "custom_status": null });
+    discord_settings(token, &body, config.retries(), dry_run)
+}
+
+fn discord_settings(token: &str, body: &serde_json::Value, retries: u32, dry_run: bool) -> Result<()> {
+    if dry_run {
+        print_dry_run_method("PATCH", DISCORD_SETTINGS_URL, body);
+        return Ok(());
+    }
+
+    // Discord user tokens go in the header as-is, with no "Bearer"
+    log_request("PATCH", DISCORD_SETTINGS_URL, &[("Authorization", token)], body);
+    let text = with_retries(retries, || {
+        ureq::patch(DISCORD_SETTINGS_URL)
+            .header("Authorization", token)
+            .send_json(body)?
+            .into_body()
+            .read_to_string()
+    })?;
+    read_response::<serde_json::Value>(&text)?;
+    Ok(())
+}
@@ -0,0 +1,78 @@
+//! Google Calendar out-of-office events for vacation, sick, and away.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate};
+use serde::Deserialize;
+use std::path::PathBuf;
+
+use crate::{
+    Config, RunOptions, Zone, format_back_date_with_time, gcal_token, log_request, print_dry_run, profile_file,
+    read_response,
+};
+
+#[derive(Deserialize)]
+struct GcalEvent {
+    id: String,
+}
+
+/// Adds an all-day out-of-office event from `start` through the day before
+/// `back` (at least `start` itself), which makes Google Calendar decline
+/// meetings that overlap it, and saves its id for a later `st back`. Returns
+/// the id. It's sent once, without retries, since a retried insert whose first
+/// try went through would leave a duplicate event.
+pub fn create_ooo_event(
+    summary: &str,
+    start: NaiveDate,
+    back: DateTime<Zone>,
+    config: &Config,
+    opts: &RunOptions,
+    dry_run: bool,
+) -> Result<String> {
+    let token = gcal_token(config, dry_run)?;
+    let calendar_id = config
+        .gcal_calendar_id
+        .as_deref()
+        .context("gcal_calendar_id not set in config")?;
+
+    let url = format!(
+        "https://www.googleapis.com/calendar/v3/calendars/{}/events",
+        calendar_id.replace('#', "%23")
+    );
+    // An all-day event's end date is exclusive: the back day itself isn't blocked
+    let end = back.date_naive().max(start + chrono::Duration::days(1));
+    let body = serde_json::json!({
+        "summary": summary,
+        "eventType": "outOfOffice",
+        "start": { "date": start.to_string() },
+        "end": { "date": end.to_string() },
+        "outOfOfficeProperties": {
+            "autoDeclineMode": "declineAllConflictingInvitations",
+            "declineMessage": format!("{summary}. {}", format_back_date_with_time(back, opts.zone.today(), config)),
+        },
+    });
+
+    if dry_run {
+        print_dry_run(&url, &body);
+        return Ok(String::new());
+    }
+
+    let auth = format!("Bearer {token}");
+    log_request("POST", &url, &[("Authorization", &auth)], &body);
+    let text = ureq::post(&url)
+        .header("Authorization", &auth)
+        .send_json(&body)?
+        .into_body()
+        .read_to_string()?;
+    let event: GcalEvent = read_response(&text)?;
+
+    let path = gcal_event_path();
+    std::fs::write(&path, &event.id)
+        .with_context(|| format!("Created event {}, but could not save its id to {}", event.id, path.display()))?;
+
+    Ok(event.id)
+}
+
+/// Where the id of the last out-of-office event is kept.
+fn gcal_event_path() -> PathBuf {
+    profile_file("gcal_event_id")
+}
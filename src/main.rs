@@ -1,664 +1,5320 @@
+mod discord;
+mod gcal;
+
 use anyhow::{Context, Result};
-use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveTime, Timelike, Weekday};
-use clap::Parser;
-use serde::Deserialize;
+use chrono::{
+    DateTime, Datelike, FixedOffset, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone,
+    Timelike, Utc, Weekday,
+};
+use clap::{CommandFactory, Parser, ValueEnum};
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use discord::{clear_discord_status, set_discord_status};
+use gcal::create_ooo_event;
 
 const DEFAULT_BACK_HOUR: u32 = 7;
+const DEFAULT_EOB_HOUR: u32 = 17;
+const DEFAULT_AFTERNOON_HOUR: u32 = 13;
+const DEFAULT_EVENING_HOUR: u32 = 17;
+const DEFAULT_TONIGHT_HOUR: u32 = 20;
+const DEFAULT_RETRIES: u32 = 3;
+const DEFAULT_CONFIRM_THRESHOLD_DAYS: u32 = 14;
+const DEFAULT_LUNCH_MINUTES: u32 = 60;
+const DEFAULT_LUNCH_ROUND_MINUTES: u32 = 15;
+const DEFAULT_FOCUS_MINUTES: u32 = 90;
+const DEFAULT_COMMUTE_MINUTES: u32 = 45;
+const DEFAULT_APPOINTMENT_MINUTES: u32 = 60;
+/// How long `--dnd` lasts when the status has no back date or `--until`
+const DEFAULT_FORCED_DND_MINUTES: i64 = 60;
+/// st.log is moved to st.log.1 once it reaches this size
+const LOG_ROTATE_BYTES: u64 = 1024 * 1024;
+/// Posted to `ooo_channel` when no `ooo_message_template` is set
+const DEFAULT_OOO_MESSAGE_TEMPLATE: &str = "I'm out until {back}.";
+/// Wait after a Slack 429 that comes without a usable `Retry-After`
+const DEFAULT_SLACK_RETRY_AFTER_SECS: u64 = 30;
 
 // --- Config ---
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
 struct Config {
+    /// GitHub keeps one status per user, scoped to at most one org, so this
+    /// takes a single id and a list is an error
+    #[serde(default, deserialize_with = "single_org_id")]
     github_org_id: Option<String>,
     asana_user_gid: Option<String>,
+    /// Looked up (and cached in state.json) in place of `asana_user_gid`
+    asana_email: Option<String>,
+    asana_workspace_gid: Option<String>,
+    /// Fallbacks for the SLACK_PAT, GITHUB_PAT, and ASANA_PAT env vars
+    slack_token: Option<String>,
+    github_token: Option<String>,
+    asana_token: Option<String>,
+    /// Fallback for the GCAL_TOKEN env var (an OAuth access token)
+    gcal_token: Option<String>,
+    /// Fallback for the DISCORD_TOKEN env var; without either, Discord is left alone
+    discord_token: Option<String>,
+    /// Google Calendar to add out-of-office events to ("primary" for your own)
+    gcal_calendar_id: Option<String>,
+    /// Slack channel ("#team" or an id) that away, vacation, and sick post to
+    ooo_channel: Option<String>,
+    /// Message posted to `ooo_channel`, with `{back}`, `{time}`, and `{keyword}`
+    ooo_message_template: Option<String>,
+    /// Hour (0-23) used when a back date is given without a time
+    default_back_hour: Option<u32>,
+    /// Hour (0-23) that the "eob"/"eod" time keyword means
+    eob_hour: Option<u32>,
+    /// Read a bare "3" as 3pm when 3am has passed (default true; turn off if you work nights).
+    /// Also accepted as `assume_pm_for_ambiguous`.
+    #[serde(alias = "assume_pm_for_ambiguous")]
+    infer_pm: Option<bool>,
+    /// Hours (0-23) for "friday morning", "friday afternoon", and "friday evening"
+    morning_hour: Option<u32>,
+    afternoon_hour: Option<u32>,
+    evening_hour: Option<u32>,
+    /// Hour (0-23) that "tonight" means as a back date
+    tonight_hour: Option<u32>,
+    #[serde(default)]
+    date_order: DateOrder,
+    /// "12h" (3:30pm, the default) or "24h" (15:30) for every time shown
+    #[serde(default)]
+    time_format: TimeFormat,
+    /// Template for the "Back Friday." text, e.g. "OOO until {weekday_short} {month}/{day}"
+    back_format: Option<String>,
+    /// End the GitHub busy status at 23:59 on the back date instead of at the back time
+    #[serde(default)]
+    github_expire_at_end_of_day: bool,
+    /// Move computed back dates that land on a weekend to the following Monday
+    #[serde(default)]
+    skip_weekends: bool,
+    /// When a back date doesn't parse, retry it with filler words and ordinal
+    /// suffixes dropped ("on dec 23rd", "friday at 3pm")
+    #[serde(default)]
+    natural_dates: bool,
+    /// Ask before setting a back date more than this many days away
+    confirm_threshold_days: Option<u32>,
+    /// How many times to retry a request after a connection failure or timeout
+    retries: Option<u32>,
+    /// `st lunch` with no time: round up to the next multiple of this many
+    /// minutes, then add the duration
+    lunch_round_to_minutes: Option<u32>,
+    lunch_duration_minutes: Option<u32>,
+    /// How long a plain `st focus` lasts
+    focus_minutes: Option<u32>,
+    /// How long a plain `st commute` lasts, after rounding like lunch
+    commute_minutes: Option<u32>,
+    /// IANA zone (e.g. "America/Chicago") that back times are given in
+    timezone: Option<String>,
+    /// Report a service whose token isn't set as skipped instead of failed
+    #[serde(default)]
+    ignore_missing_tokens: bool,
+    /// Shell commands run after a status is set or cleared, with ST_KEYWORD and
+    /// ST_BACK_DATE in the environment
+    on_set_hook: Option<String>,
+    on_clear_hook: Option<String>,
+    /// Append a line per status change to st.log
+    #[serde(default)]
+    logging: bool,
+    /// Office closures ("2025-12-25") that computed back dates move past
+    #[serde(default)]
+    holidays: Vec<String>,
+    /// Named back times ("after standup" = "09:30") usable wherever a time is
+    #[serde(default)]
+    times: BTreeMap<String, String>,
+    /// Other keywords for statuses ("mtg" = "meet"), on top of the built-in ones
+    #[serde(default)]
+    aliases: BTreeMap<String, String>,
+    #[serde(default, rename = "status")]
+    statuses: Vec<Status>,
+    /// `[statuses.<keyword>]` tables, merged into `statuses` once checked
+    #[serde(default, rename = "statuses")]
+    status_tables: BTreeMap<String, StatusTable>,
+    /// Why a `[statuses.<keyword>]` table was left out
+    #[serde(skip)]
+    status_table_problems: Vec<String>,
+}
+
+/// Settings for this run that aren't in config.toml: resolved from it at
+/// startup, or set by flags. Passed alongside `&Config`.
+#[derive(Default)]
+struct RunOptions {
+    /// Resolved from `timezone` (or `--tz`)
+    zone: Zone,
+    /// Resolved from `holidays`; unparseable entries are dropped
+    holiday_dates: Vec<NaiveDate>,
+    /// Set by `--allow-past`: accept back dates that have already passed
+    allow_past: bool,
+    /// Set by `--skip-scope-check`: don't check the GitHub and Slack tokens' scopes first
+    skip_scope_check: bool,
+    /// Set by `--skip-emoji-check`: don't look the status emoji up in Slack first
+    skip_emoji_check: bool,
+    /// Set by `--dnd` on a status that doesn't expire: how long DND lasts,
+    /// while the status itself stays up
+    forced_dnd_minutes: Option<i64>,
+    /// Set by `--json`, `--quiet`, and `--color`
+    output: OutputMode,
+}
+
+/// A `[statuses.<keyword>]` table. Everything is optional here so a bad entry
+/// can be skipped with a warning instead of failing the whole config, and so a
+/// built-in keyword's table only needs the fields it changes.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StatusTable {
+    slack_text: Option<String>,
+    slack_emoji: Option<String>,
+    slack_dnd: Option<bool>,
+    github_busy: Option<bool>,
+    description: Option<String>,
+    default_back_time: Option<String>,
+    back_template: Option<String>,
+}
+
+#[derive(Deserialize, Default, Clone, Copy, PartialEq)]
+enum TimeFormat {
+    #[default]
+    #[serde(rename = "12h")]
+    H12,
+    #[serde(rename = "24h")]
+    H24,
+}
+
+/// Reads `github_org_id`, turning a list away with the reason rather than
+/// serde's bare type error.
+fn single_org_id<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    match toml::Value::deserialize(deserializer)? {
+        toml::Value::String(id) => Ok(Some(id)),
+        toml::Value::Array(_) => Err(serde::de::Error::custom(
+            "github_org_id takes one org id: GitHub keeps one status per user, scoped to at most one org",
+        )),
+        other => Err(serde::de::Error::custom(format!(
+            "github_org_id must be an org id string, got {}",
+            other.type_str()
+        ))),
+    }
+}
+
+/// How numeric dates like `3/10` are read: March 10 (`mdy`) or 3 October (`dmy`).
+#[derive(Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum DateOrder {
+    #[default]
+    Mdy,
+    Dmy,
+}
+
+impl Config {
+    fn retries(&self) -> u32 {
+        self.retries.unwrap_or(DEFAULT_RETRIES)
+    }
+
+    /// Moves valid `[statuses.<keyword>]` tables into `statuses`, noting why
+    /// any others were left out. A table for a built-in keyword overrides just
+    /// the fields it sets and keeps the rest of the built-in.
+    fn merge_status_tables(&mut self) {
+        for (keyword, table) in std::mem::take(&mut self.status_tables) {
+            let keyword = keyword.to_lowercase();
+            let builtin = STATUSES.iter().find(|s| s.keyword == keyword);
+            if table.slack_text.as_deref().is_some_and(|text| text.trim().is_empty()) {
+                self.status_table_problems.push(format!("[statuses.{keyword}] slack_text is empty"));
+                continue;
+            }
+            let slack_text = table.slack_text.or_else(|| builtin.map(|s| s.slack_text.to_string()));
+            let Some(slack_text) = slack_text else {
+                self.status_table_problems.push(format!("[statuses.{keyword}] has no slack_text"));
+                continue;
+            };
+            let slack_emoji = table
+                .slack_emoji
+                .or_else(|| builtin.map(|s| s.slack_emoji.to_string()))
+                .unwrap_or_default();
+            if !is_emoji_code(&slack_emoji) {
+                self.status_table_problems.push(format!(
+                    "[statuses.{keyword}] slack_emoji must look like :name:, got {slack_emoji:?}"
+                ));
+                continue;
+            }
+            let inherit = |field: fn(&Status) -> &Option<Cow<'static, str>>| builtin.and_then(|s| field(s).clone());
+            self.statuses.push(Status {
+                slack_text: Cow::Owned(slack_text),
+                slack_emoji: Cow::Owned(slack_emoji),
+                slack_dnd: table.slack_dnd.or(builtin.map(|s| s.slack_dnd)).unwrap_or(false),
+                github_busy: table.github_busy.or(builtin.map(|s| s.github_busy)).unwrap_or(false),
+                asana_dnd: builtin.is_some_and(|s| s.asana_dnd),
+                description: table.description.map(Cow::Owned).or_else(|| inherit(|s| &s.description)),
+                default_back_time: table.default_back_time.map(Cow::Owned).or_else(|| inherit(|s| &s.default_back_time)),
+                back_template: table.back_template.map(Cow::Owned).or_else(|| inherit(|s| &s.back_template)),
+                keyword: Cow::Owned(keyword),
+            });
+        }
+    }
+
+    /// Looks up a `[times]` entry, ignoring case and treating hyphens as spaces.
+    fn named_time(&self, name: &str) -> Option<(&str, &str)> {
+        let normalize = |s: &str| s.trim().to_lowercase().replace('-', " ");
+        let name = normalize(name);
+        self.times
+            .iter()
+            .find(|(key, _)| normalize(key) == name)
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+}
+
+/// ":palm_tree:", ":+1:": a Slack emoji code, colons and all.
+fn is_emoji_code(emoji: &str) -> bool {
+    emoji
+        .strip_prefix(':')
+        .and_then(|rest| rest.strip_suffix(':'))
+        .is_some_and(|name| !name.is_empty() && !name.contains(':') && !name.contains(char::is_whitespace))
+}
+
+/// Unicode for the Slack emoji codes the built-in statuses use, for services
+/// that take the emoji itself. Anything else that isn't a `:name:` passes through.
+const UNICODE_EMOJI: [(&str, &str); 14] = [
+    (":fork_and_knife:", "\u{1F374}"),
+    (":video_camera:", "\u{1F4F9}"),
+    (":couple:", "\u{1F46B}"),
+    (":calendar:", "\u{1F4C6}"),
+    (":wave:", "\u{1F44B}"),
+    (":desert_island:", "\u{1F3DD}\u{FE0F}"),
+    (":face_with_thermometer:", "\u{1F912}"),
+    (":no_entry:", "\u{26D4}"),
+    (":inbox_tray:", "\u{1F4E5}"),
+    (":speech_balloon:", "\u{1F4AC}"),
+    (":headphones:", "\u{1F3A7}"),
+    (":bus:", "\u{1F68C}"),
+    (":hospital:", "\u{1F3E5}"),
+    (":microphone:", "\u{1F3A4}"),
+];
+
+/// An `--emoji` value as Slack takes it: ":palm_tree:" as is, "palm_tree" with
+/// colons added, and an emoji character as its code when known, else unchanged.
+fn parse_emoji_arg(input: &str) -> Result<String> {
+    let input = input.trim();
+    if is_emoji_code(input) {
+        return Ok(input.to_string());
+    }
+    if !input.is_empty() && input.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+')) {
+        return Ok(format!(":{input}:"));
+    }
+    if !input.is_empty() && !input.chars().any(|c| c.is_ascii() || c.is_whitespace()) {
+        let code = UNICODE_EMOJI.iter().find(|(_, emoji)| *emoji == input).map(|(code, _)| *code);
+        return Ok(code.unwrap_or(input).to_string());
+    }
+    anyhow::bail!("Invalid emoji: {input:?}\nExamples: :palm_tree:, palm_tree, \u{1F334}")
+}
+
+/// The emoji character for a Slack emoji code, `None` for a code it doesn't know.
+fn unicode_emoji(slack_emoji: &str) -> Option<&str> {
+    if !is_emoji_code(slack_emoji) {
+        return Some(slack_emoji).filter(|e| !e.is_empty());
+    }
+    UNICODE_EMOJI
+        .iter()
+        .find(|(code, _)| *code == slack_emoji)
+        .map(|(_, emoji)| *emoji)
+}
+
+/// Set by `--profile`: read `<name>.toml` instead of config.toml.
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// A profile name is a file name, so it's kept to letters, digits, `-`, and `_`.
+fn set_profile(name: &str) -> Result<()> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_')) {
+        anyhow::bail!("Invalid profile name: {name:?}\nUse letters, digits, - and _ only");
+    }
+    let _ = PROFILE.set(name.to_string());
+    Ok(())
 }
 
 fn config_path() -> PathBuf {
+    let file = match PROFILE.get() {
+        Some(name) => format!("{name}.toml"),
+        None => "config.toml".to_string(),
+    };
     dirs::home_dir()
         .unwrap_or_else(|| PathBuf::from("."))
         .join(".config")
         .join("st")
-        .join("config.toml")
+        .join(file)
+}
+
+/// A file kept next to the config, with the profile name added so each
+/// profile keeps its own ("state.json" becomes "side.state.json").
+fn profile_file(name: &str) -> PathBuf {
+    let file = match PROFILE.get() {
+        Some(profile) => format!("{profile}.{name}"),
+        None => name.to_string(),
+    };
+    config_path().with_file_name(file)
+}
+
+/// The env var a token is read from: SLACK_PAT, or SLACK_PAT_SIDE under `--profile side`.
+fn token_var(var: &str) -> String {
+    match PROFILE.get() {
+        Some(profile) => format!("{var}_{}", profile.to_uppercase().replace('-', "_")),
+        None => var.to_string(),
+    }
 }
 
-fn load_config() -> Config {
+/// Parses config.toml as written. A missing file is an empty config (but a
+/// missing `--profile` file is an error); anything unparseable, including an
+/// unknown key, is an error.
+fn read_config() -> Result<Config> {
     let path = config_path();
     match std::fs::read_to_string(&path) {
-        Ok(contents) => toml::from_str(&contents).unwrap_or_else(|e| {
-            eprintln!("Warning: failed to parse {}: {e}", path.display());
-            Config {
-                github_org_id: None,
-                asana_user_gid: None,
-            }
-        }),
-        Err(_) => Config {
-            github_org_id: None,
-            asana_user_gid: None,
+        Ok(contents) => {
+            let mut config: Config =
+                toml::from_str(&contents).with_context(|| format!("Invalid config {}", path.display()))?;
+            config.merge_status_tables();
+            Ok(config)
+        }
+        Err(_) => match PROFILE.get() {
+            Some(name) => anyhow::bail!("Profile {name} not found: create {}", path.display()),
+            None => Ok(Config::default()),
         },
     }
 }
 
-// --- Date/time parsing ---
+/// Reads the config, warning about (and dropping) values it can't use, and
+/// resolves the time zone and holidays into the run's options.
+fn load_config() -> Result<(Config, RunOptions)> {
+    let path = config_path();
+    let mut config = read_config()?;
+    let mut opts = RunOptions::default();
 
-fn parse_back_date(date_str: &str, time_str: Option<&str>) -> Result<DateTime<Local>> {
-    let today = Local::now().date_naive();
-    let lower = date_str.to_lowercase();
+    if let Some(hour) = config.default_back_hour
+        && hour > 23
+    {
+        eprintln!(
+            "Warning: default_back_hour must be 0-23 in {}, got {hour}; using {DEFAULT_BACK_HOUR}",
+            path.display()
+        );
+        config.default_back_hour = None;
+    }
 
-    // Day names: "monday", "tuesday", etc. — next occurrence
-    let weekday = match lower.as_str() {
-        "monday" | "mon" => Some(Weekday::Mon),
-        "tuesday" | "tue" | "tues" => Some(Weekday::Tue),
-        "wednesday" | "wed" => Some(Weekday::Wed),
-        "thursday" | "thu" | "thurs" => Some(Weekday::Thu),
-        "friday" | "fri" => Some(Weekday::Fri),
-        "saturday" | "sat" => Some(Weekday::Sat),
-        "sunday" | "sun" => Some(Weekday::Sun),
-        "tomorrow" => {
-            let date = today + chrono::Duration::days(1);
-            return Ok(to_local_datetime(date, parse_time(time_str)?));
+    for (key, value) in [
+        ("lunch_round_to_minutes", &mut config.lunch_round_to_minutes),
+        ("lunch_duration_minutes", &mut config.lunch_duration_minutes),
+        ("focus_minutes", &mut config.focus_minutes),
+        ("commute_minutes", &mut config.commute_minutes),
+    ] {
+        if *value == Some(0) {
+            eprintln!("Warning: {key} must be at least 1 in {}; using the default", path.display());
+            *value = None;
         }
-        _ => None,
-    };
+    }
 
-    let date = if let Some(day) = weekday {
-        let today_weekday = today.weekday().num_days_from_monday();
-        let target = day.num_days_from_monday();
-        let delta = if target > today_weekday {
-            target - today_weekday
-        } else {
-            7 - today_weekday + target
-        };
-        today + chrono::Duration::days(delta as i64)
-    } else if let Some(date) = parse_date_with_separators(date_str, today) {
-        date
-    } else {
-        anyhow::bail!(
-            "Could not parse date: {date_str}\nExamples: friday, 3/10, 3-10-2026, tomorrow"
-        );
-    };
+    if let Some(name) = &config.timezone {
+        match Zone::parse(name) {
+            Ok(zone) => opts.zone = zone,
+            Err(e) => eprintln!("Warning: timezone in {}: {e}\nUsing the system time zone", path.display()),
+        }
+    }
 
-    Ok(to_local_datetime(date, parse_time(time_str)?))
-}
+    for problem in &config.status_table_problems {
+        eprintln!("Warning: {problem} in {}; skipping it", path.display());
+    }
 
-fn parse_date_with_separators(input: &str, today: NaiveDate) -> Option<NaiveDate> {
-    // Split on / or -
-    let parts: Vec<&str> = input.split(&['/', '-'][..]).collect();
+    let templates = config
+        .back_format
+        .iter()
+        .map(|t| ("back_format".to_string(), t.as_str()))
+        .chain(config.statuses.iter().filter_map(|s| {
+            let template = s.back_template.as_deref()?;
+            Some((format!("back_template for {}", s.keyword), template))
+        }));
+    for (key, template) in templates {
+        for name in unknown_placeholders(template) {
+            eprintln!(
+                "Warning: unknown placeholder {{{name}}} in {key} in {}\nAvailable: {{{}}}",
+                path.display(),
+                BACK_FORMAT_PLACEHOLDERS.join("}, {")
+            );
+        }
+    }
 
-    match parts.len() {
-        // M/D or M-D
-        2 => {
-            let month = parts[0].parse::<u32>().ok()?;
-            let day = parts[1].parse::<u32>().ok()?;
-            let mut year = today.year();
-            let date = NaiveDate::from_ymd_opt(year, month, day)?;
-            if date < today {
-                year += 1;
-            }
-            NaiveDate::from_ymd_opt(year, month, day)
+    if let Some(template) = &config.ooo_message_template {
+        for name in unknown_ooo_placeholders(template) {
+            eprintln!(
+                "Warning: unknown placeholder {{{name}}} in ooo_message_template in {}\nAvailable: {{{}}}",
+                path.display(),
+                OOO_MESSAGE_PLACEHOLDERS.join("}, {")
+            );
         }
-        // M/D/Y or M-D-Y (2-digit or 4-digit year)
-        3 => {
-            let month = parts[0].parse::<u32>().ok()?;
-            let day = parts[1].parse::<u32>().ok()?;
-            let mut year = parts[2].parse::<i32>().ok()?;
-            if year < 100 {
-                year += 2000;
-            }
-            NaiveDate::from_ymd_opt(year, month, day)
+    }
+
+    for holiday in &config.holidays {
+        match NaiveDate::parse_from_str(holiday.trim(), "%Y-%m-%d") {
+            Ok(date) => opts.holiday_dates.push(date),
+            Err(_) => eprintln!(
+                "Warning: ignoring holiday {holiday:?} in {}, expected YYYY-MM-DD",
+                path.display()
+            ),
         }
-        _ => None,
     }
+
+    let bad_aliases: Vec<String> = config
+        .aliases
+        .iter()
+        .filter_map(|(alias, target)| {
+            let problem = alias_problem(&config, alias, target)?;
+            eprintln!("Warning: ignoring {problem} in {}", path.display());
+            Some(alias.clone())
+        })
+        .collect();
+    for alias in bad_aliases {
+        config.aliases.remove(&alias);
+    }
+
+    Ok((config, opts))
 }
 
-fn parse_time(input: Option<&str>) -> Result<NaiveTime> {
-    let input = match input {
-        Some(s) => s,
-        None => return Ok(NaiveTime::from_hms_opt(DEFAULT_BACK_HOUR, 0, 0).unwrap()),
-    };
+/// Everything `st config check` flags beyond parse errors: out-of-range hours,
+/// an unknown time zone, duplicate statuses, and unreadable `[times]` entries.
+fn config_problems(config: &Config) -> Vec<String> {
+    let mut problems = Vec::new();
+    let hours = [
+        ("default_back_hour", config.default_back_hour),
+        ("eob_hour", config.eob_hour),
+        ("morning_hour", config.morning_hour),
+        ("afternoon_hour", config.afternoon_hour),
+        ("evening_hour", config.evening_hour),
+        ("tonight_hour", config.tonight_hour),
+    ];
+    for (key, hour) in hours {
+        if let Some(hour) = hour.filter(|h| *h > 23) {
+            problems.push(format!("{key} must be 0-23, got {hour}"));
+        }
+    }
+    if config.lunch_round_to_minutes == Some(0) {
+        problems.push("lunch_round_to_minutes must be at least 1".to_string());
+    }
+    if config.lunch_duration_minutes == Some(0) {
+        problems.push("lunch_duration_minutes must be at least 1".to_string());
+    }
+    if config.focus_minutes == Some(0) {
+        problems.push("focus_minutes must be at least 1".to_string());
+    }
+    if config.commute_minutes == Some(0) {
+        problems.push("commute_minutes must be at least 1".to_string());
+    }
+    if let Some(name) = &config.timezone
+        && let Err(e) = Zone::parse(name)
+    {
+        problems.push(format!("timezone: {e}"));
+    }
+    problems.extend(config.status_table_problems.iter().cloned());
+    if let Err(e) = check_duplicate_statuses(config) {
+        problems.push(e.to_string());
+    }
+    for status in &config.statuses {
+        for name in status.back_template.as_deref().map(unknown_placeholders).unwrap_or_default() {
+            problems.push(format!("[[status]] {} back_template: unknown placeholder {{{name}}}", status.keyword));
+        }
+        if let Some(time) = &status.default_back_time
+            && let Err(e) = parse_time(Some(time), config)
+        {
+            problems.push(format!("[[status]] {} default_back_time: {e}", status.keyword));
+        }
+    }
+    for (name, value) in &config.times {
+        if NaiveTime::parse_from_str(value.trim(), "%H:%M").is_err() {
+            problems.push(format!("[times] \"{name}\" must be HH:MM, got {value}"));
+        }
+    }
+    if let Some(template) = &config.back_format {
+        for name in unknown_placeholders(template) {
+            problems.push(format!("back_format: unknown placeholder {{{name}}}"));
+        }
+    }
+    if let Some(template) = &config.ooo_message_template {
+        for name in unknown_ooo_placeholders(template) {
+            problems.push(format!("ooo_message_template: unknown placeholder {{{name}}}"));
+        }
+    }
+    for holiday in &config.holidays {
+        if NaiveDate::parse_from_str(holiday.trim(), "%Y-%m-%d").is_err() {
+            problems.push(format!("holidays: {holiday:?} must be YYYY-MM-DD"));
+        }
+    }
+    problems.extend(
+        config
+            .aliases
+            .iter()
+            .filter_map(|(alias, target)| alias_problem(config, alias, target)),
+    );
+    problems
+}
 
-    let s = input.to_lowercase();
-    let s = s.trim();
+/// An alias can't shadow a status or a command keyword, and has to point at a status.
+fn alias_problem(config: &Config, alias: &str, target: &str) -> Option<String> {
+    let alias = alias.to_lowercase();
+    if find_status(config, &alias).is_some() || COMMAND_KEYWORDS.contains(&alias.as_str()) {
+        return Some(format!("[aliases] {alias}: {alias} is already a keyword"));
+    }
+    if find_status(config, &target.to_lowercase()).is_none() {
+        return Some(format!("[aliases] {alias}: unknown keyword {target}"));
+    }
+    None
+}
 
-    // Strip am/pm suffix and track it
-    let (num_part, is_pm) = if let Some(rest) = s.strip_suffix("pm") {
-        (rest.trim(), Some(true))
-    } else if let Some(rest) = s.strip_suffix("p.m.") {
-        (rest.trim(), Some(true))
-    } else if let Some(rest) = s.strip_suffix("am") {
-        (rest.trim(), Some(false))
-    } else if let Some(rest) = s.strip_suffix("a.m.") {
-        (rest.trim(), Some(false))
+fn check_duplicate_statuses(config: &Config) -> Result<()> {
+    for (i, status) in config.statuses.iter().enumerate() {
+        if config.statuses[..i].iter().any(|s| s.keyword == status.keyword) {
+            anyhow::bail!(
+                "Duplicate status keyword in {}: {}",
+                config_path().display(),
+                status.keyword
+            );
+        }
+    }
+    Ok(())
+}
+
+// --- Time zone ---
+
+/// Zone that back times are read and shown in: the system's, or a named one
+/// from `--tz` / `timezone` in config.
+#[derive(Clone, Copy, Debug, Default)]
+enum Zone {
+    #[default]
+    Local,
+    Named(chrono_tz::Tz),
+}
+
+impl Zone {
+    fn parse(name: &str) -> Result<Zone> {
+        name.parse::<chrono_tz::Tz>().map(Zone::Named).map_err(|_| {
+            anyhow::anyhow!("Unknown time zone: {name}\nUse an IANA name like America/Chicago or Europe/London")
+        })
+    }
+
+    fn now(self) -> DateTime<Zone> {
+        Utc::now().with_timezone(&self)
+    }
+
+    fn today(self) -> NaiveDate {
+        self.now().date_naive()
+    }
+}
+
+/// Offset of a `Zone` at some instant. Keeps the zone so date math stays in it.
+#[derive(Clone, Copy, Debug)]
+struct ZoneOffset {
+    zone: Zone,
+    fixed: FixedOffset,
+}
+
+impl Offset for ZoneOffset {
+    fn fix(&self) -> FixedOffset {
+        self.fixed
+    }
+}
+
+impl std::fmt::Display for ZoneOffset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fixed.fmt(f)
+    }
+}
+
+impl TimeZone for Zone {
+    type Offset = ZoneOffset;
+
+    fn from_offset(offset: &ZoneOffset) -> Zone {
+        offset.zone
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<ZoneOffset> {
+        self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN))
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<ZoneOffset> {
+        let fixed = match self {
+            Zone::Local => Local.offset_from_local_datetime(local),
+            Zone::Named(tz) => tz.offset_from_local_datetime(local).map(|o| o.fix()),
+        };
+        fixed.map(|fixed| ZoneOffset { zone: *self, fixed })
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> ZoneOffset {
+        self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> ZoneOffset {
+        let fixed = match self {
+            Zone::Local => Local.offset_from_utc_datetime(utc),
+            Zone::Named(tz) => tz.offset_from_utc_datetime(utc).fix(),
+        };
+        ZoneOffset { zone: *self, fixed }
+    }
+}
+
+// --- Date/time parsing ---
+
+/// `default_time` is the status's own back time, used when no time is given;
+/// without one, `default_back_hour` applies. `now` is `opts.zone.now()`
+/// outside of tests. Alongside the date comes a note for the user when the
+/// input was read in a way they might not expect.
+fn parse_back_date(
+    date_str: &str,
+    time_str: Option<&str>,
+    default_time: Option<&str>,
+    now: DateTime<Zone>,
+    config: &Config,
+    opts: &RunOptions,
+) -> Result<(DateTime<Zone>, Option<String>)> {
+    let today = now.date_naive();
+    // "tonight" is today at `tonight_hour`, and never moves off a weekend or holiday
+    let tonight = date_str.trim().eq_ignore_ascii_case("tonight");
+    let (date, period_time, names_today) = if tonight {
+        let hour = config.tonight_hour.unwrap_or(DEFAULT_TONIGHT_HOUR);
+        let time =
+            NaiveTime::from_hms_opt(hour, 0, 0).with_context(|| format!("Invalid tonight_hour in config: {hour}"))?;
+        (today, Some(time), false)
     } else {
-        (s, None)
+        let (day_str, period_time) = split_day_period(date_str, config)?;
+        let day_lower = day_str.trim().to_lowercase();
+        let date = parse_date(day_str, today, config)?;
+        let names_today = parse_weekday(&day_lower) == Some(today.weekday());
+        (adjust_back_day(date, &day_lower, today, config, opts), period_time, names_today)
     };
 
-    // Parse hour and optional minutes
-    let (hour, minute): (u32, u32) = if let Some((h, m)) = num_part.split_once(':') {
-        (h.parse()?, m.parse()?)
-    } else {
-        (num_part.parse()?, 0)
+    // An explicit back time wins over "morning"/"afternoon"/"evening", which win
+    // over the status's default
+    let time = match (time_str, period_time) {
+        (None, Some(time)) => time,
+        (None, None) => parse_time(default_time, config)?,
+        (Some(_), _) => parse_time(time_str, config)?,
     };
+    let mut dt = infer_pm(to_local_datetime(date, time, config, opts), time_str, now, config, opts, false);
+    let mut note = None;
 
-    // Apply AM/PM
-    let hour = match is_pm {
-        Some(true) if hour < 12 => hour + 12,
-        Some(false) if hour == 12 => 0,
-        _ => hour,
+    // A bare day name that is today ("friday" on a Friday) means next week,
+    // unless it comes with a time that's still ahead today ("friday 5pm")
+    if names_today {
+        let today_dt = infer_pm(to_local_datetime(today, time, config, opts), time_str, now, config, opts, false);
+        if (time_str.is_some() || period_time.is_some()) && today_dt > now {
+            dt = today_dt;
+        } else {
+            note = Some(format!(
+                "{} is today, using next week's {}/{}",
+                date.format("%A"),
+                date.month(),
+                date.day()
+            ));
+        }
+    }
+
+    if tonight && !opts.allow_past && dt < now {
+        anyhow::bail!(
+            "It's already past {} tonight\nGive a later time (e.g., tonight 11pm) or pass --allow-past",
+            format_time(dt, config)
+        );
+    }
+    if !opts.allow_past && dt < now {
+        anyhow::bail!(
+            "Back date {date_str} is in the past ({} {})\nPass --allow-past to use it anyway",
+            dt.format("%a %b %-d, %Y"),
+            format_time(dt, config)
+        );
+    }
+
+    Ok((dt, note))
+}
+
+/// Like `parse_back_date`, but also accepts a leave range ("3/10-3/20" or
+/// "3/10 to 3/20") and returns its first day alongside the back date. With
+/// `natural_dates`, input that doesn't parse gets a second, looser try, whose
+/// reading comes back as the note.
+fn parse_back_range(
+    date_str: &str,
+    time_str: Option<&str>,
+    default_time: Option<&str>,
+    now: DateTime<Zone>,
+    config: &Config,
+    opts: &RunOptions,
+) -> Result<(Option<NaiveDate>, DateTime<Zone>, Option<String>)> {
+    let strict = parse_strict_back_range(date_str, time_str, default_time, now, config, opts);
+    let Err(e) = strict else {
+        return strict;
+    };
+    let Some((natural_date, natural_time, weekday)) =
+        config.natural_dates.then(|| naturalize_date(date_str, config)).flatten()
+    else {
+        return Err(e);
     };
 
-    NaiveTime::from_hms_opt(hour, minute, 0)
-        .ok_or_else(|| anyhow::anyhow!("Invalid time: {input}"))
+    let time_str = time_str.or(natural_time.as_deref());
+    let (start, back, _) =
+        parse_strict_back_range(&natural_date, time_str, default_time, now, config, opts).map_err(|_| e)?;
+    // The dropped weekday has to agree with the date, or one of them is a typo
+    let first = start.unwrap_or(back.date_naive());
+    if weekday.is_some_and(|weekday| weekday != first.weekday()) {
+        anyhow::bail!("Could not parse date: {date_str}\n{} is a {}", first.format("%B %-d"), first.format("%A"));
+    }
+    let note = format!("interpreted '{date_str}' as {} {}", back.format("%a %-m/%-d"), format_time(back, config));
+    Ok((start, back, Some(note)))
 }
 
-fn to_local_datetime(date: NaiveDate, time: NaiveTime) -> DateTime<Local> {
-    date.and_time(time)
-        .and_local_timezone(Local)
-        .unwrap()
+/// Loosens a back date for `natural_dates`: drops filler words ("on", "the",
+/// "of", "coming", "at") and ordinal suffixes ("23rd"), and splits off a
+/// trailing time and a leading weekday, which the caller checks against the
+/// date. `None` when there's nothing to loosen.
+fn naturalize_date(input: &str, config: &Config) -> Option<(String, Option<String>, Option<Weekday>)> {
+    const FILLER: [&str; 9] = ["on", "the", "of", "coming", "at", "by", "until", "til", "till"];
+    let lower = input.to_lowercase().replace(',', " ");
+    let mut words: Vec<&str> = lower.split_whitespace().filter(|w| !FILLER.contains(w)).collect();
+
+    let time = match words.as_slice() {
+        [_, .., last] if looks_like_time(last, config) => words.pop().map(str::to_string),
+        _ => None,
+    };
+    // "monday oct 19" -> "oct 19": the date already says which day it is
+    let weekday = if words.len() > 2 { parse_weekday(words[0]) } else { None };
+    if weekday.is_some() {
+        words.remove(0);
+    }
+    // "dec 23rd" -> "dec 23"; a lone "23rd" is already understood
+    if words.len() > 1 {
+        for word in &mut words {
+            if let Some(day) = ["st", "nd", "rd", "th"].iter().find_map(|suffix| word.strip_suffix(suffix))
+                && !day.is_empty()
+                && day.chars().all(|c| c.is_ascii_digit())
+            {
+                *word = day;
+            }
+        }
+    }
+
+    let date = words.join(" ");
+    (!date.is_empty() && (date != lower.trim() || time.is_some())).then_some((date, time, weekday))
 }
 
-fn format_back_date(dt: DateTime<Local>) -> String {
-    let today = Local::now().date_naive();
-    let date = dt.date_naive();
-    let days_away = (date - today).num_days();
+fn parse_strict_back_range(
+    date_str: &str,
+    time_str: Option<&str>,
+    default_time: Option<&str>,
+    now: DateTime<Zone>,
+    config: &Config,
+    opts: &RunOptions,
+) -> Result<(Option<NaiveDate>, DateTime<Zone>, Option<String>)> {
+    let today = now.date_naive();
 
-    if days_away <= 7 {
-        format!("Back {}.", date.format("%A"))
-    } else {
-        format!("Back {}/{}.", date.month(), date.day())
+    // Durations ("30m", "1h30m") count from now, e.g. for meetings
+    if parse_duration(date_str).is_some() {
+        return Ok((None, parse_back_duration(date_str, now)?, None));
+    }
+
+    // A lone time ("3pm", "15:30", "noon") means today at that time
+    if time_str.is_none() && looks_like_time(date_str, config) {
+        let time = parse_time(Some(date_str), config)?;
+        // "st eod midnight" means tonight's, which is tomorrow's 00:00
+        let date = if time == NaiveTime::MIN {
+            today + chrono::Duration::days(1)
+        } else {
+            today
+        };
+        let dt = infer_pm(to_local_datetime(date, time, config, opts), Some(date_str), now, config, opts, false);
+        if !opts.allow_past && dt < now {
+            anyhow::bail!(
+                "Back time {date_str} has already passed today\nGive a date too (e.g., tomorrow {date_str}) or pass --allow-past"
+            );
+        }
+        return Ok((None, dt, None));
+    }
+
+    if parse_date(date_str, today, config).is_err()
+        && let Some((start, end_str)) = parse_date_range(date_str, today, config)
+    {
+        let (back, note) = parse_back_date(end_str, time_str, default_time, now, config, opts)?;
+        if back.date_naive() <= start {
+            anyhow::bail!(
+                "Invalid range {date_str}: back date {}/{} must be after the start {}/{}",
+                back.month(),
+                back.day(),
+                start.month(),
+                start.day()
+            );
+        }
+        return Ok((Some(start), back, note));
     }
+
+    let (back, note) = parse_back_date(date_str, time_str, default_time, now, config, opts)?;
+    Ok((None, back, note))
 }
 
-fn format_back_date_with_time(dt: DateTime<Local>) -> String {
-    let today = Local::now().date_naive();
-    let date = dt.date_naive();
-    let days_away = (date - today).num_days();
-    let time = format_time(dt);
+/// Back date for a plain "st eod": the next workday (past weekends and holidays)
+/// at the usual back hour, or the status's own `default_back_time`, so the
+/// status and DND lift when work starts.
+fn default_eod_back(status: &Status, today: NaiveDate, config: &Config, opts: &RunOptions) -> Option<DateTime<Zone>> {
+    let mut date = skip_weekend(today + chrono::Duration::days(1));
+    while opts.holiday_dates.contains(&date) {
+        date = skip_weekend(date + chrono::Duration::days(1));
+    }
+    let time = parse_time(status.default_back_time.as_deref(), config).ok()?;
+    Some(to_local_datetime(date, time, config, opts))
+}
 
-    if days_away <= 7 {
-        format!("Back {} {}.", date.format("%A"), time)
-    } else {
-        format!("Back {}/{} {}.", date.month(), date.day(), time)
+/// Whether a back date argument is really a time: an am/pm suffix, "HH:MM", or
+/// a time keyword or `[times]` name. Bare numbers are left to the date parser.
+fn looks_like_time(input: &str, config: &Config) -> bool {
+    let s = input.trim().to_lowercase();
+    matches!(s.as_str(), "noon" | "12n" | "midnight" | "12m" | "eob" | "eod")
+        || config.named_time(&s).is_some()
+        || s.contains(':')
+        || ["am", "pm", "a.m.", "p.m."].iter().any(|suffix| s.ends_with(suffix))
+}
+
+/// "30m", "1h", "90m", "1h30m". The h/m suffix is what sets these apart from
+/// times, except for "12m", which is midnight.
+fn parse_duration(input: &str) -> Option<chrono::Duration> {
+    let lower = input.trim().to_lowercase();
+    if lower == "12m" {
+        return None;
+    }
+    let (hours, rest) = match lower.split_once('h') {
+        Some((h, rest)) => (h.parse::<u32>().ok()?, rest),
+        None => (0, lower.as_str()),
+    };
+    let minutes = match rest {
+        "" => 0,
+        m => m.strip_suffix('m')?.parse::<u32>().ok()?,
+    };
+    if rest.is_empty() && !lower.contains('h') {
+        return None;
     }
+    Some(chrono::Duration::hours(hours.into()) + chrono::Duration::minutes(minutes.into()))
 }
 
-fn format_time(dt: DateTime<Local>) -> String {
-    let hour = dt.format("%I").to_string().trim_start_matches('0').to_string();
-    let minute = dt.minute();
-    let ampm = dt.format("%p").to_string().to_lowercase();
+/// Back time for a duration from now, as given to `--for` or in place of a date.
+fn parse_back_duration(input: &str, now: DateTime<Zone>) -> Result<DateTime<Zone>> {
+    let duration = parse_duration(input)
+        .with_context(|| format!("Could not parse duration: {input}\nExamples: 30m, 2h, 90m, 1h30m"))?;
+    if duration <= chrono::Duration::zero() {
+        anyhow::bail!("Duration must be greater than zero: {input}");
+    }
+    now.checked_add_signed(duration)
+        .with_context(|| format!("Duration is too long: {input}"))
+}
 
-    if minute == 0 {
-        format!("{}{}", hour, ampm)
-    } else {
-        format!("{}:{:02}{}", hour, minute, ampm)
+/// Splits "3/10-3/20" or "3/10 to 3/20" into the parsed start and the raw end.
+/// Each dash is tried as the split point, since dates may contain dashes too.
+fn parse_date_range<'a>(input: &'a str, today: NaiveDate, config: &Config) -> Option<(NaiveDate, &'a str)> {
+    if let Some((start, end)) = input.split_once(" to ") {
+        return Some((parse_date(start, today, config).ok()?, end.trim()));
     }
+
+    input.match_indices('-').find_map(|(i, _)| {
+        let (start, end) = (&input[..i], &input[i + 1..]);
+        let start = parse_date(start, today, config).ok()?;
+        parse_date(end, today, config).ok()?;
+        Some((start, end))
+    })
 }
 
-fn parse_lunch_back_time(input: Option<&str>) -> Result<DateTime<Local>> {
-    let today = Local::now().date_naive();
-    let time = match input {
-        Some(s) => parse_time(Some(s))?,
-        None => {
-            // Next quarter hour + 1 hour
-            let now = Local::now();
-            let min = now.minute();
-            let next_quarter = ((min / 15) + 1) * 15;
-            let round_up = (next_quarter - min) as i64;
-            let back = now + chrono::Duration::minutes(round_up + 60);
-            return Ok(back);
-        }
+/// Splits a trailing "morning", "afternoon", or "evening" off a date ("friday
+/// afternoon", "monday-morning") and resolves it to the configured hour.
+fn split_day_period<'a>(input: &'a str, config: &Config) -> Result<(&'a str, Option<NaiveTime>)> {
+    let trimmed = input.trim();
+    let Some((day, period)) = trimmed.rsplit_once(&[' ', '-'][..]) else {
+        return Ok((input, None));
     };
-    Ok(to_local_datetime(today, time))
-}
 
-// --- Status definitions ---
+    let (name, hour) = match period.to_lowercase().as_str() {
+        "morning" => (
+            "morning_hour",
+            config
+                .morning_hour
+                .or(config.default_back_hour)
+                .unwrap_or(DEFAULT_BACK_HOUR),
+        ),
+        "afternoon" => ("afternoon_hour", config.afternoon_hour.unwrap_or(DEFAULT_AFTERNOON_HOUR)),
+        "evening" => ("evening_hour", config.evening_hour.unwrap_or(DEFAULT_EVENING_HOUR)),
+        _ => return Ok((input, None)),
+    };
 
-struct Status {
-    keyword: &'static str,
-    slack_text: &'static str,
-    slack_emoji: &'static str,
-    slack_dnd: bool,
-    github_busy: bool,
-    #[allow(dead_code)]
-    asana_dnd: bool, // Asana API doesn't support setting OOO yet
+    let time = NaiveTime::from_hms_opt(hour, 0, 0).with_context(|| format!("Invalid {name} in config: {hour}"))?;
+    Ok((day.trim(), Some(time)))
 }
 
-const STATUSES: &[Status] = &[
+fn parse_date(input: &str, today: NaiveDate, config: &Config) -> Result<NaiveDate> {
+    let lower = input.trim().to_lowercase();
+
+    // Extra spaces don't matter here: "day  after tomorrow"
+    let words = lower.replace('-', " ").split_whitespace().collect::<Vec<_>>().join(" ");
+    match words.as_str() {
+        "today" => return Ok(today),
+        "tomorrow" => return Ok(today + chrono::Duration::days(1)),
+        "day after tomorrow" | "overmorrow" => return Ok(today + chrono::Duration::days(2)),
+        "next week" => return Ok(next_weekday(today, Weekday::Mon)),
+        "end of week" => return Ok(weekday_this_week(today, Weekday::Fri)),
+        "end of month" => return Ok(end_of_month(today)),
+        _ => {}
+    }
+
+    // Relative offsets: "+3", "3d"
+    if let Some(days) = parse_day_offset(&lower) {
+        return offset_date(today, 0, days, input);
+    }
+
+    // Week offsets: "2w", "1w3d"
+    if let Some((weeks, days)) = parse_week_offset(&lower) {
+        if weeks == 0 {
+            anyhow::bail!("Could not parse date: {input}\nThe number of weeks must be at least 1");
+        }
+        return offset_date(today, weeks, days, input);
+    }
+
+    // "in 3 days", "in 2 weeks"
+    if let Some(rest) = lower.strip_prefix("in ") {
+        let (weeks, days) = parse_in_duration(rest.trim())?;
+        return offset_date(today, weeks, days, input);
+    }
+
+    // Weeks past another date: "a week from friday", "2 weeks from friday", "friday+1w"
+    let offset_usage =
+        || format!("Could not parse date: {input}\nExamples: a week from friday, two weeks from friday, friday+1w");
+    if let Some((count, base)) = lower.split_once(" from ") {
+        let weeks = parse_week_count(count.trim()).with_context(offset_usage)?;
+        return offset_date(parse_date(base, today, config)?, weeks, 0, input);
+    }
+    if let Some((base, offset)) = lower.split_once('+').filter(|(base, _)| !base.trim().is_empty()) {
+        let (weeks, days) = parse_week_offset(offset.trim()).with_context(offset_usage)?;
+        return offset_date(parse_date(base, today, config)?, weeks, days, input);
+    }
+
+    // "next friday" / "this friday" (or joined: "next-friday")
+    if let Some((modifier @ ("next" | "this"), rest)) = lower.split_once(&[' ', '-'][..]) {
+        let day = parse_weekday(rest.trim()).with_context(|| {
+            let hint = suggest_date_word(&lower)
+                .map(|word| format!(" \u{2014} did you mean '{word}'?"))
+                .unwrap_or_default();
+            format!("Could not parse date: {input}{hint}\nExpected a day name after \"{modifier}\"")
+        })?;
+        return Ok(if modifier == "next" {
+            weekday_next_week(today, day)
+        } else {
+            weekday_this_week(today, day)
+        });
+    }
+
+    // Day names: "monday", "tuesday", etc. — next occurrence
+    if let Some(day) = parse_weekday(&lower) {
+        return Ok(next_weekday(today, day));
+    }
+
+    // Month names: "march 10", "mar10", "10 march", "march-10"
+    if let Some(date) = parse_month_name_date(&lower, today) {
+        return Ok(date);
+    }
+
+    // Ordinals: "15th", "the 1st"
+    if let Some(day) = parse_ordinal_day(&lower) {
+        return Ok(upcoming_day_of_month(today, day));
+    }
+
+    parse_date_with_separators(&lower, today, config.date_order).with_context(|| {
+        let numeric = match config.date_order {
+            DateOrder::Mdy => "3/10, 3-10-2026",
+            DateOrder::Dmy => "10/3, 10-3-2026",
+        };
+        let hint = match (suggest_date_word(&lower), numeric_date_problem(&lower, config.date_order)) {
+            (Some(word), _) => format!(" \u{2014} did you mean '{word}'?"),
+            (None, Some(problem)) => format!(" ({problem})"),
+            (None, None) => String::new(),
+        };
+        format!(
+            "Could not parse date: {input}{hint}\nExamples: today, tonight, tomorrow, day after tomorrow, friday, next friday, next week, end of month, +3, 2w, in 3 days, in 2 weeks, a week from friday, {numeric}, march 10, 15th, 2026-03-10"
+        )
+    })
+}
+
+/// Closest date word to a typo ("firday" -> "friday"), keeping a leading
+/// "next"/"this". Only close matches count, so real garbage gets no suggestion.
+fn suggest_date_word(input: &str) -> Option<String> {
+    const WORDS: [&str; 24] = [
+        "today", "tonight", "tomorrow", "next week", "end of week", "end of month",
+        "monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday",
+        "january", "february", "march", "april", "june", "july", "august", "september",
+        "october", "november", "december",
+    ];
+    let (prefix, word) = match input.split_once(' ') {
+        Some((modifier @ ("next" | "this"), rest)) => (format!("{modifier} "), rest.trim()),
+        _ => (String::new(), input),
+    };
+    if word.len() < 3 {
+        return None;
+    }
+    WORDS
+        .iter()
+        .map(|candidate| (edit_distance(word, candidate), candidate))
+        .filter(|(distance, _)| *distance > 0 && *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| format!("{prefix}{candidate}"))
+}
+
+/// Levenshtein distance: insertions, deletions, and substitutions.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// Names the part of a numeric date ("3//10", "3-", "13/40") that's wrong.
+fn numeric_date_problem(input: &str, order: DateOrder) -> Option<String> {
+    if !input.contains(['/', '-']) || !input.chars().all(|c| c.is_ascii_digit() || c == '/' || c == '-') {
+        return None;
+    }
+    let parts: Vec<&str> = input.split(&['/', '-'][..]).collect();
+    let labels: &[&str] = match (parts.len(), order) {
+        (3, _) if parts[0].len() == 4 => &["year", "month", "day"],
+        (2 | 3, DateOrder::Mdy) => &["month", "day", "year"],
+        (2 | 3, DateOrder::Dmy) => &["day", "month", "year"],
+        _ => return Some("expected at most three parts".to_string()),
+    };
+    for (label, part) in labels.iter().zip(&parts) {
+        let Ok(value) = part.parse::<u32>() else {
+            return Some(format!("missing {label}"));
+        };
+        match *label {
+            "month" if !(1..=12).contains(&value) => return Some(format!("month must be 1-12, got {value}")),
+            "day" if !(1..=31).contains(&value) => return Some(format!("day must be 1-31, got {value}")),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_month_name_date(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let compact: String = input.chars().filter(|c| !matches!(c, ' ' | '-')).collect();
+    let day_first = compact.starts_with(|c: char| c.is_ascii_digit());
+    let split = compact.find(|c: char| c.is_ascii_digit() != day_first)?;
+    let (first, second) = compact.split_at(split);
+
+    let (month_str, day_str) = if day_first {
+        (second, first)
+    } else {
+        (first, second)
+    };
+
+    let month = parse_month(month_str)?;
+    if !day_str.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let day = day_str.parse::<u32>().ok()?;
+    upcoming_month_day(today, month, day)
+}
+
+/// "1st" through "31st", optionally after "the". The suffix has to match the
+/// number, so "15st" is rejected.
+fn parse_ordinal_day(input: &str) -> Option<u32> {
+    let input = input.strip_prefix("the ").unwrap_or(input).trim();
+    let split = input.find(|c: char| !c.is_ascii_digit())?;
+    let (digits, suffix) = input.split_at(split);
+    let day = digits.parse::<u32>().ok().filter(|d| (1..=31).contains(d))?;
+    let expected = match (day % 10, day % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    (suffix == expected).then_some(day)
+}
+
+/// This month's `day`, or the next month that has it once it has passed. A day
+/// the month doesn't have rolls forward too: the 31st in April is May 31.
+fn upcoming_day_of_month(today: NaiveDate, day: u32) -> NaiveDate {
+    let first = today.with_day(1).unwrap();
+    (0..3)
+        .filter_map(|months| first.checked_add_months(chrono::Months::new(months))?.with_day(day))
+        .find(|date| *date >= today)
+        .unwrap()
+}
+
+fn parse_month(input: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "january", "february", "march", "april", "may", "june",
+        "july", "august", "september", "october", "november", "december",
+    ];
+    let input = if input == "sept" { "sep" } else { input };
+    let index = MONTHS
+        .iter()
+        .position(|m| *m == input || (input.len() == 3 && m.starts_with(input)))?;
+    Some(index as u32 + 1)
+}
+
+/// (month, day) readings of two numeric date parts: the configured order first,
+/// then the swapped one so `13/10` still parses when only one reading is legal.
+fn month_day_candidates(first: u32, second: u32, order: DateOrder) -> [(u32, u32); 2] {
+    match order {
+        DateOrder::Mdy => [(first, second), (second, first)],
+        DateOrder::Dmy => [(second, first), (first, second)],
+    }
+}
+
+/// Last day of this month, or of next month if today already is the last day.
+fn end_of_month(today: NaiveDate) -> NaiveDate {
+    let last_day = |date: NaiveDate| {
+        let first = date.with_day(1).unwrap();
+        let next_first = first.checked_add_months(chrono::Months::new(1)).unwrap();
+        next_first.pred_opt().unwrap()
+    };
+    let end = last_day(today);
+    if end == today {
+        last_day(today + chrono::Duration::days(1))
+    } else {
+        end
+    }
+}
+
+/// Month/day in the current year, or next year if it has already passed.
+fn upcoming_month_day(today: NaiveDate, month: u32, day: u32) -> Option<NaiveDate> {
+    let date = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+    if date < today {
+        NaiveDate::from_ymd_opt(today.year() + 1, month, day)
+    } else {
+        Some(date)
+    }
+}
+
+/// Day count from "+3" or "3d". "+0" means today.
+fn parse_day_offset(input: &str) -> Option<i64> {
+    let num = input.strip_prefix('+').or_else(|| input.strip_suffix('d'))?;
+    if num.is_empty() || !num.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    num.parse().ok()
+}
+
+/// Weeks and extra days from "2w" or "1w3d".
+fn parse_week_offset(input: &str) -> Option<(i64, i64)> {
+    let (weeks, rest) = input.split_once('w')?;
+    let is_number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    if !is_number(weeks) {
+        return None;
+    }
+    let days = match rest {
+        "" => 0,
+        d => d.strip_suffix('d').filter(|d| is_number(d))?.parse().ok()?,
+    };
+    Some((weeks.parse().ok()?, days))
+}
+
+/// The "a week" / "two weeks" / "3 weeks" part of "... from friday".
+fn parse_week_count(input: &str) -> Option<i64> {
+    let (count, unit) = input.split_once(char::is_whitespace)?;
+    if !matches!(unit.trim(), "week" | "weeks") {
+        return None;
+    }
+    let count = match count {
+        "a" | "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        n => n.parse().ok()?,
+    };
+    (count >= 1).then_some(count)
+}
+
+/// `date` plus some weeks and days, or an error when that's past the last date
+/// chrono can represent ("in 99999999999 days").
+fn offset_date(date: NaiveDate, weeks: i64, days: i64, input: &str) -> Result<NaiveDate> {
+    chrono::Duration::try_weeks(weeks)
+        .zip(chrono::Duration::try_days(days))
+        .and_then(|(weeks, days)| weeks.checked_add(&days))
+        .and_then(|offset| date.checked_add_signed(offset))
+        .with_context(|| format!("Could not parse date: {input}\nThat's too far out"))
+}
+
+/// Weeks and days from the "3 days" / "1 week" / "2 weeks" part of "in ...".
+fn parse_in_duration(input: &str) -> Result<(i64, i64)> {
+    let usage = || format!("Could not parse date: in {input}\nExamples: in 3 days, in 1 week, in 2 weeks");
+
+    let (num, unit) = input.split_once(char::is_whitespace).with_context(usage)?;
+    let count: i64 = num.parse().ok().with_context(usage)?;
+    if count < 1 {
+        anyhow::bail!("Could not parse date: in {input}\nThe number must be at least 1");
+    }
+
+    match unit.trim() {
+        "day" | "days" => Ok((0, count)),
+        "week" | "weeks" => Ok((count, 0)),
+        _ => Err(anyhow::anyhow!(usage())),
+    }
+}
+
+/// Moves a back date off a weekend (with `skip_weekends`) and off configured
+/// holidays, onto the next workday. Explicit day names ("saturday", "next
+/// sunday") are honored as asked, and typed dates ("12/25") only get a warning.
+fn adjust_back_day(date: NaiveDate, input: &str, today: NaiveDate, config: &Config, opts: &RunOptions) -> NaiveDate {
+    let is_holiday = |d: NaiveDate| opts.holiday_dates.contains(&d);
+    let named_day = names_weekday(input);
+    let named_date = named_day || names_calendar_date(input, today, config);
+
+    if named_date {
+        if is_holiday(date) {
+            eprintln!("Note: {} {}/{} is a holiday", date.format("%A"), date.month(), date.day());
+        }
+        if named_day {
+            return date;
+        }
+    }
+
+    let mut adjusted = date;
+    if config.skip_weekends {
+        adjusted = skip_weekend(adjusted);
+    }
+    if !named_date {
+        while is_holiday(adjusted) {
+            adjusted = skip_weekend(adjusted + chrono::Duration::days(1));
+        }
+    }
+
+    if adjusted != date {
+        eprintln!(
+            "Note: {} {}/{} is a {}, using {} {}/{}",
+            date.format("%A"),
+            date.month(),
+            date.day(),
+            if is_holiday(date) { "holiday" } else { "weekend" },
+            adjusted.format("%A"),
+            adjusted.month(),
+            adjusted.day()
+        );
+    }
+    adjusted
+}
+
+/// Dates written out as a calendar day ("12/25", "dec 25", "25th"), as opposed
+/// to relative ones like "tomorrow" or "+3".
+fn names_calendar_date(input: &str, today: NaiveDate, config: &Config) -> bool {
+    parse_month_name_date(input, today).is_some()
+        || parse_ordinal_day(input).is_some()
+        || parse_date_with_separators(input, today, config.date_order).is_some()
+}
+
+/// Whether the input is a day name, with or without a "next"/"this" modifier.
+fn names_weekday(input: &str) -> bool {
+    let day = match input.split_once(&[' ', '-'][..]) {
+        Some(("next" | "this", rest)) => rest.trim(),
+        _ => input,
+    };
+    parse_weekday(day).is_some()
+}
+
+/// Saturday and Sunday move to the following Monday; other days are unchanged.
+fn skip_weekend(date: NaiveDate) -> NaiveDate {
+    match date.weekday() {
+        Weekday::Sat => date + chrono::Duration::days(2),
+        Weekday::Sun => date + chrono::Duration::days(1),
+        _ => date,
+    }
+}
+
+fn parse_weekday(input: &str) -> Option<Weekday> {
+    match input {
+        "monday" | "mon" => Some(Weekday::Mon),
+        "tuesday" | "tue" | "tues" => Some(Weekday::Tue),
+        "wednesday" | "wed" => Some(Weekday::Wed),
+        "thursday" | "thu" | "thurs" => Some(Weekday::Thu),
+        "friday" | "fri" => Some(Weekday::Fri),
+        "saturday" | "sat" => Some(Weekday::Sat),
+        "sunday" | "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Next occurrence of `day` after today (a week out if today is `day`).
+fn next_weekday(today: NaiveDate, day: Weekday) -> NaiveDate {
+    let today_weekday = today.weekday().num_days_from_monday();
+    let target = day.num_days_from_monday();
+    let delta = if target > today_weekday {
+        target - today_weekday
+    } else {
+        7 - today_weekday + target
+    };
+    today + chrono::Duration::days(delta as i64)
+}
+
+/// Nearest `day` on or after today ("this friday" on a Friday is today).
+fn weekday_this_week(today: NaiveDate, day: Weekday) -> NaiveDate {
+    let today_weekday = today.weekday().num_days_from_monday();
+    let target = day.num_days_from_monday();
+    let delta = (target + 7 - today_weekday) % 7;
+    today + chrono::Duration::days(delta as i64)
+}
+
+/// "next friday": one week past the next occurrence, so on a Wednesday it's 9 days out, not 2.
+fn weekday_next_week(today: NaiveDate, day: Weekday) -> NaiveDate {
+    next_weekday(today, day) + chrono::Duration::days(7)
+}
+
+fn parse_date_with_separators(input: &str, today: NaiveDate, order: DateOrder) -> Option<NaiveDate> {
+    // Split on / or -
+    let parts: Vec<&str> = input.split(&['/', '-'][..]).collect();
+
+    match parts.len() {
+        // Y-M-D or Y/M/D (ISO style, 4-digit year first)
+        3 if parts[0].len() == 4 => {
+            let year = parts[0].parse::<i32>().ok()?;
+            let month = parts[1].parse::<u32>().ok()?;
+            let day = parts[2].parse::<u32>().ok()?;
+            NaiveDate::from_ymd_opt(year, month, day)
+        }
+        // M/D or M-D (D/M with date_order = "dmy")
+        2 => {
+            let first = parts[0].parse::<u32>().ok()?;
+            let second = parts[1].parse::<u32>().ok()?;
+            month_day_candidates(first, second, order)
+                .into_iter()
+                .find_map(|(month, day)| upcoming_month_day(today, month, day))
+        }
+        // M/D/Y or M-D-Y (2-digit or 4-digit year)
+        3 => {
+            let first = parts[0].parse::<u32>().ok()?;
+            let second = parts[1].parse::<u32>().ok()?;
+            let mut year = parts[2].parse::<i32>().ok()?;
+            if year < 100 {
+                year += 2000;
+            }
+            month_day_candidates(first, second, order)
+                .into_iter()
+                .find_map(|(month, day)| NaiveDate::from_ymd_opt(year, month, day))
+        }
+        _ => None,
+    }
+}
+
+fn parse_time(input: Option<&str>, config: &Config) -> Result<NaiveTime> {
+    let input = match input {
+        Some(s) => s,
+        None => {
+            let hour = config.default_back_hour.unwrap_or(DEFAULT_BACK_HOUR);
+            return Ok(NaiveTime::from_hms_opt(hour, 0, 0).unwrap());
+        }
+    };
+
+    // Config names win over the built-in keywords below
+    if let Some((name, value)) = config.named_time(input) {
+        return NaiveTime::parse_from_str(value.trim(), "%H:%M")
+            .with_context(|| format!("Invalid time for \"{name}\" in [times]: {value}\nExpected HH:MM, e.g. 09:30"));
+    }
+
+    let s = input.to_lowercase();
+    let s = s.trim();
+
+    match s {
+        "noon" | "12n" => return Ok(NaiveTime::from_hms_opt(12, 0, 0).unwrap()),
+        "midnight" | "12m" => return Ok(NaiveTime::MIN),
+        "eob" | "eod" => {
+            let hour = config.eob_hour.unwrap_or(DEFAULT_EOB_HOUR);
+            return NaiveTime::from_hms_opt(hour, 0, 0)
+                .with_context(|| format!("Invalid eob_hour in config: {hour}"));
+        }
+        _ => {}
+    }
+
+    let invalid = || {
+        let mut msg = format!("Could not parse time: {input}\nExamples: 9am, 1:30pm, 15:00, 1530, noon, midnight, eob");
+        if !config.times.is_empty() {
+            let names: Vec<&str> = config.times.keys().map(String::as_str).collect();
+            msg.push_str(&format!("\nNamed times: {}", names.join(", ")));
+        }
+        msg
+    };
+
+    // Strip am/pm suffix and track it
+    let (num_part, is_pm) = if let Some(rest) = s.strip_suffix("pm") {
+        (rest.trim(), Some(true))
+    } else if let Some(rest) = s.strip_suffix("p.m.") {
+        (rest.trim(), Some(true))
+    } else if let Some(rest) = s.strip_suffix("am") {
+        (rest.trim(), Some(false))
+    } else if let Some(rest) = s.strip_suffix("a.m.") {
+        (rest.trim(), Some(false))
+    } else {
+        (s, None)
+    };
+
+    // Parse hour and optional minutes
+    let (hour, minute): (u32, u32) = if let Some((h, m)) = num_part.split_once(':') {
+        // Zero-padded 24-hour times ("08:30", "00:15") parse like any other; minutes
+        // must be two digits so "9:5" isn't silently read as 9:05
+        if m.len() != 2 {
+            anyhow::bail!("Invalid time '{input}': minutes must be two digits\nExamples: 9:05am, 00:15, 23:59");
+        }
+        (h.parse().ok().with_context(invalid)?, m.parse().ok().with_context(invalid)?)
+    } else if (3..=4).contains(&num_part.len()) && num_part.chars().all(|c| c.is_ascii_digit()) {
+        // Military time: "930" is 9:30, "1530" is 15:30
+        let (h, m) = num_part.split_at(num_part.len() - 2);
+        (h.parse()?, m.parse()?)
+    } else {
+        (num_part.parse().ok().with_context(invalid)?, 0)
+    };
+
+    let out_of_range = |reason: &str| {
+        anyhow::anyhow!("Invalid time '{input}': {reason}\nExamples: 9am, 1:30pm, 15:00, 1530")
+    };
+    if minute > 59 {
+        return Err(out_of_range("minutes must be 0\u{2013}59"));
+    }
+    match is_pm {
+        Some(_) if !(1..=12).contains(&hour) => {
+            return Err(out_of_range("hours must be 1\u{2013}12 with am/pm"));
+        }
+        None if hour > 23 => return Err(out_of_range("hours must be 0\u{2013}23")),
+        _ => {}
+    }
+
+    // Apply AM/PM
+    let hour = match is_pm {
+        Some(true) if hour < 12 => hour + 12,
+        Some(false) if hour == 12 => 0,
+        _ => hour,
+    };
+
+    NaiveTime::from_hms_opt(hour, minute, 0)
+        .ok_or_else(|| anyhow::anyhow!("Invalid time: {input}"))
+}
+
+/// Resolves a wall-clock time in the local zone. Ambiguous fall-back times use
+/// the earlier instant; times in a spring-forward gap are pushed past it.
+fn to_local_datetime(date: NaiveDate, time: NaiveTime, config: &Config, opts: &RunOptions) -> DateTime<Zone> {
+    let zone = opts.zone;
+    let naive = date.and_time(time);
+    match naive.and_local_timezone(zone) {
+        LocalResult::Single(dt) => dt,
+        // The two instants don't always come in order (chrono's Local gives the later first)
+        LocalResult::Ambiguous(a, b) => a.min(b),
+        LocalResult::None => {
+            let dt = skip_dst_gap(naive, zone);
+            eprintln!(
+                "Note: {} doesn't exist on {}/{} (DST), using {}",
+                format_naive_time(time, config),
+                date.month(),
+                date.day(),
+                format_time(dt, config)
+            );
+            dt
+        }
+    }
+}
+
+/// Shifts a nonexistent local time forward by the size of the DST gap, so
+/// 2:30am on a spring-forward day becomes 3:30am.
+fn skip_dst_gap(naive: NaiveDateTime, zone: Zone) -> DateTime<Zone> {
+    let offset_at = |n: NaiveDateTime| n.and_local_timezone(zone).earliest().map(|dt| dt.offset().fix());
+    let gap = match (offset_at(naive - chrono::Duration::days(1)), offset_at(naive + chrono::Duration::days(1))) {
+        (Some(before), Some(after)) => after.local_minus_utc() - before.local_minus_utc(),
+        _ => 0,
+    };
+
+    if gap > 0
+        && let Some(dt) = (naive + chrono::Duration::seconds(gap as i64)).and_local_timezone(zone).earliest()
+    {
+        return dt;
+    }
+
+    // Unusual zone rules: take the first valid minute after the gap
+    (1..=24 * 60)
+        .find_map(|m| (naive + chrono::Duration::minutes(m)).and_local_timezone(zone).earliest())
+        .expect("local time gap longer than a day")
+}
+
+/// "Back Friday.", or just "Back 8pm." when the back date is `today`.
+fn format_back_date(dt: DateTime<Zone>, today: NaiveDate, config: &Config) -> String {
+    match &config.back_format {
+        Some(template) => render_back_format(template, dt, today, "", None),
+        None if dt.date_naive() == today => format!("Back {}.", format_time(dt, config)),
+        None => format!("Back {}.", back_day_label(dt.date_naive(), today)),
+    }
+}
+
+fn format_back_date_with_time(dt: DateTime<Zone>, today: NaiveDate, config: &Config) -> String {
+    match &config.back_format {
+        Some(template) => render_back_format(template, dt, today, "", Some(format_time(dt, config))),
+        None if dt.date_naive() == today => format!("Back {}.", format_time(dt, config)),
+        None => format!("Back {} {}.", back_day_label(dt.date_naive(), today), format_time(dt, config)),
+    }
+}
+
+const BACK_FORMAT_PLACEHOLDERS: [&str; 8] = [
+    "weekday", "weekday_short", "month", "day", "year", "date", "time", "status",
+];
+
+/// Fills in a `back_format` or `back_template` template like "OOO until
+/// {weekday_short} {date}". `{time}` is left empty when the time isn't shown,
+/// and the gap it leaves is closed.
+fn render_back_format(
+    template: &str,
+    dt: DateTime<Zone>,
+    today: NaiveDate,
+    status_text: &str,
+    time: Option<String>,
+) -> String {
+    let date = dt.date_naive();
+    let text = template
+        .replace("{status}", status_text)
+        .replace("{date}", &format_month_day(date, today))
+        .replace("{weekday}", &date.format("%A").to_string())
+        .replace("{weekday_short}", &date.format("%a").to_string())
+        .replace("{month}", &date.month().to_string())
+        .replace("{day}", &date.day().to_string())
+        .replace("{year}", &date.year().to_string())
+        .replace("{time}", &time.unwrap_or_default());
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// "Vacation. Back Friday.", or the status's own `back_template` filled in.
+fn status_back_text(status: &Status, dt: DateTime<Zone>, with_time: bool, config: &Config) -> String {
+    if let Some(text) = expand_status_text(&status.slack_text, Some(dt), config) {
+        return text;
+    }
+    let today = dt.timezone().today();
+    match &status.back_template {
+        Some(template) => {
+            render_back_format(template, dt, today, &status.slack_text, with_time.then(|| format_time(dt, config)))
+        }
+        None if with_time => format!("{}. {}", status.slack_text, format_back_date_with_time(dt, today, config)),
+        None => format!("{}. {}", status.slack_text, format_back_date(dt, today, config)),
+    }
+}
+
+/// The status text with `{back}` and `{time}` filled in, or as written when it
+/// has neither.
+fn plain_status_text(status: &Status, back_date: Option<DateTime<Zone>>, config: &Config) -> String {
+    expand_status_text(&status.slack_text, back_date, config).unwrap_or_else(|| status.slack_text.to_string())
+}
+
+/// Fills in a status text like "OOO \u{2014} back {back}": `{back}` is the back
+/// date ("Friday", "Friday 1pm", or "3pm" today) and `{time}` the back time.
+/// Without a back date each placeholder goes, along with the "until"/"back"
+/// or dash leading up to it. `None` when the text has no placeholders.
+fn expand_status_text(text: &str, back_date: Option<DateTime<Zone>>, config: &Config) -> Option<String> {
+    if !text.contains("{back}") && !text.contains("{time}") {
+        return None;
+    }
+    if let Some(dt) = back_date {
+        return Some(fill_back_placeholders(text, dt, dt.timezone().today(), config));
+    }
+
+    const LEAD_INS: [&str; 7] = ["until", "til", "till", "back", "at", "by", "returning"];
+    let is_lead_in = |word: &str| {
+        let bare = word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+        LEAD_INS.contains(&bare.as_str()) || bare.is_empty()
+    };
+    let mut words: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        if word.contains("{back}") || word.contains("{time}") {
+            while words.last().is_some_and(|w| is_lead_in(w)) {
+                words.pop();
+            }
+            // Punctuation after the placeholder ("until {back}, ping me") stays
+            let rest = word.replace("{back}", "").replace("{time}", "");
+            if let Some(last) = words.last_mut()
+                && rest.starts_with([',', '.', ';', '!'])
+                && !rest.chars().any(char::is_alphanumeric)
+            {
+                last.push_str(&rest);
+            }
+        } else {
+            words.push(word.to_string());
+        }
+    }
+    while words.last().is_some_and(|w| w.chars().all(|c| !c.is_alphanumeric())) {
+        words.pop();
+    }
+    if let Some(last) = words.last_mut() {
+        last.truncate(last.trim_end_matches([',', ';', ':', '-', '\u{2013}', '\u{2014}']).len());
+    }
+    Some(words.join(" "))
+}
+
+/// Fills in `{back}` and `{time}` for a back date, naming its day relative to `today`.
+fn fill_back_placeholders(text: &str, dt: DateTime<Zone>, today: NaiveDate, config: &Config) -> String {
+    let default_hour = config.default_back_hour.unwrap_or(DEFAULT_BACK_HOUR);
+    let back = match dt.date_naive() {
+        date if date == today => format_time(dt, config),
+        date if dt.hour() == default_hour && dt.minute() == 0 => back_day_label(date, today),
+        date => format!("{} {}", back_day_label(date, today), format_time(dt, config)),
+    };
+    text.replace("{back}", &back).replace("{time}", &format_time(dt, config))
+}
+
+const OOO_MESSAGE_PLACEHOLDERS: [&str; 3] = ["back", "time", "keyword"];
+
+/// Placeholders in `ooo_message_template` that `ooo_message` doesn't know.
+fn unknown_ooo_placeholders(template: &str) -> Vec<&str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+        .filter(|name| !OOO_MESSAGE_PLACEHOLDERS.contains(name))
+        .collect()
+}
+
+/// Placeholders in a `back_format` template that `render_back_format` doesn't know.
+fn unknown_placeholders(template: &str) -> Vec<&str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+        .filter(|name| !BACK_FORMAT_PLACEHOLDERS.contains(name))
+        .collect()
+}
+
+/// The weekday within the coming week, plus the date a week out where the
+/// weekday alone would read as today ("Monday 3/17"), and the date beyond that.
+fn back_day_label(date: NaiveDate, today: NaiveDate) -> String {
+    match (date - today).num_days() {
+        0..=6 => date.format("%A").to_string(),
+        7 => format!("{} {}", date.format("%A"), format_month_day(date, today)),
+        _ => format_month_day(date, today),
+    }
+}
+
+/// "3/10", with the year appended once the date is a year or more away (either way).
+fn format_month_day(date: NaiveDate, today: NaiveDate) -> String {
+    if (date - today).num_days().abs() < 365 {
+        format!("{}/{}", date.month(), date.day())
+    } else {
+        format!("{}/{}/{}", date.month(), date.day(), date.year())
+    }
+}
+
+fn format_time(dt: DateTime<Zone>, config: &Config) -> String {
+    format_naive_time(dt.time(), config)
+}
+
+fn format_naive_time(time: NaiveTime, config: &Config) -> String {
+    if config.time_format == TimeFormat::H24 {
+        return time.format("%H:%M").to_string();
+    }
+
+    let hour = time.format("%I").to_string().trim_start_matches('0').to_string();
+    let minute = time.minute();
+    let ampm = time.format("%p").to_string().to_lowercase();
+
+    if minute == 0 {
+        format!("{}{}", hour, ampm)
+    } else {
+        format!("{}:{:02}{}", hour, minute, ampm)
+    }
+}
+
+/// "1:30pm" for later today, "Tuesday 1am" this week, "3/14 1am" further out.
+fn format_until(dt: DateTime<Zone>, config: &Config) -> String {
+    let today = dt.timezone().today();
+    let date = dt.date_naive();
+    match (date - today).num_days() {
+        0 => format_time(dt, config),
+        1..=6 => format!("{} {}", date.format("%A"), format_time(dt, config)),
+        _ => format!("{} {}", format_month_day(date, today), format_time(dt, config)),
+    }
+}
+
+fn format_expiration(dt: DateTime<Zone>, config: &Config) -> String {
+    format!(" (until {} {}/{} {})", dt.format("%a"), dt.month(), dt.day(), format_time(dt, config))
+}
+
+fn parse_lunch_back_time(
+    input: Option<&str>,
+    now: DateTime<Zone>,
+    config: &Config,
+    opts: &RunOptions,
+) -> Result<DateTime<Zone>> {
+    let today = now.date_naive();
+    if let Some(s) = input.filter(|s| parse_duration(s).is_some()) {
+        return parse_back_duration(s, now);
+    }
+    let time = match input {
+        Some(s) => parse_time(Some(s), config)?,
+        None => {
+            let round_to = config.lunch_round_to_minutes.unwrap_or(DEFAULT_LUNCH_ROUND_MINUTES);
+            let duration = config.lunch_duration_minutes.unwrap_or(DEFAULT_LUNCH_MINUTES);
+            return Ok(default_lunch_back(now, round_to, duration));
+        }
+    };
+    // Lunch is always today, so "st lunch 1" means 1pm, and midnight is tonight's
+    if time == NaiveTime::MIN {
+        return Ok(to_local_datetime(today + chrono::Duration::days(1), time, config, opts));
+    }
+    Ok(infer_pm(to_local_datetime(today, time, config, opts), input, now, config, opts, true))
+}
+
+/// `st focus` takes how long first: "2h", "45" (minutes), or a time today
+/// ("3pm"). With nothing, it's a `focus_minutes` block from now.
+fn parse_focus_back_time(
+    input: Option<&str>,
+    now: DateTime<Zone>,
+    config: &Config,
+    opts: &RunOptions,
+) -> Result<DateTime<Zone>> {
+    match input {
+        None => {
+            let minutes = config.focus_minutes.unwrap_or(DEFAULT_FOCUS_MINUTES);
+            Ok(now + chrono::Duration::minutes(minutes.into()))
+        }
+        Some(s) if !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) => parse_back_duration(&format!("{s}m"), now),
+        Some(s) => parse_lunch_back_time(Some(s), now, config, opts),
+    }
+}
+
+/// `st commute` with nothing is a `commute_minutes` trip, rounded like lunch so
+/// it ends on a round minute. "30" and "30m" are how long; "9am" is when you're
+/// back online (tomorrow, once it has passed today).
+fn parse_commute_back_time(
+    input: Option<&str>,
+    now: DateTime<Zone>,
+    config: &Config,
+    opts: &RunOptions,
+) -> Result<DateTime<Zone>> {
+    match input {
+        None => {
+            let round_to = config.lunch_round_to_minutes.unwrap_or(DEFAULT_LUNCH_ROUND_MINUTES);
+            let duration = config.commute_minutes.unwrap_or(DEFAULT_COMMUTE_MINUTES);
+            Ok(default_lunch_back(now, round_to, duration))
+        }
+        Some(s) if !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) => parse_back_duration(&format!("{s}m"), now),
+        Some(s) if parse_duration(s).is_some() => parse_back_duration(s, now),
+        Some(s) => parse_until(s, now, config, opts),
+    }
+}
+
+/// `--until`: a wall-clock time today, or tomorrow if it has already passed.
+fn parse_until(input: &str, now: DateTime<Zone>, config: &Config, opts: &RunOptions) -> Result<DateTime<Zone>> {
+    let time = parse_time(Some(input), config)?;
+    let today = now.date_naive();
+    let dt = to_local_datetime(today, time, config, opts);
+    if dt > now {
+        Ok(dt)
+    } else {
+        Ok(to_local_datetime(today + chrono::Duration::days(1), time, config, opts))
+    }
+}
+
+/// A bare hour from 1 to 6 ("3", "3:30") with no am/pm.
+fn is_bare_early_hour(input: &str) -> bool {
+    let input = input.trim();
+    let hour = input.split_once(':').map_or(input, |(hour, _)| hour);
+    input.chars().all(|c| c.is_ascii_digit() || c == ':')
+        && !hour.starts_with('0')
+        && hour.parse::<u32>().is_ok_and(|h| (1..=6).contains(&h))
+}
+
+/// Reads a bare early hour as pm when am has already passed (or always, for a
+/// same-day status), since "back at 3" rarely means 3 in the morning. A time on
+/// a future day is kept as given. Set `infer_pm = false` to turn this off.
+fn infer_pm(
+    dt: DateTime<Zone>,
+    input: Option<&str>,
+    now: DateTime<Zone>,
+    config: &Config,
+    opts: &RunOptions,
+    same_day: bool,
+) -> DateTime<Zone> {
+    match input {
+        Some(s) if config.infer_pm.unwrap_or(true) && is_bare_early_hour(s) && (same_day || dt < now) => {
+            let pm = to_local_datetime(dt.date_naive(), dt.time() + chrono::Duration::hours(12), config, opts);
+            eprintln!("Note: reading {} as {}", s.trim(), format_time(pm, config));
+            pm
+        }
+        _ => dt,
+    }
+}
+
+/// Rounds `now` up to the next multiple of `round_to` minutes past the hour
+/// (always moving forward, so 12:00 becomes 12:15) and adds `duration` minutes.
+fn default_lunch_back(now: DateTime<Zone>, round_to: u32, duration: u32) -> DateTime<Zone> {
+    let min = now.minute();
+    let next = ((min / round_to) + 1) * round_to;
+    let round_up = i64::from(next - min);
+    now + chrono::Duration::minutes(round_up + i64::from(duration))
+}
+
+// --- Status definitions ---
+
+#[derive(Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+struct Status {
+    keyword: Cow<'static, str>,
+    slack_text: Cow<'static, str>,
+    slack_emoji: Cow<'static, str>,
+    #[serde(default)]
+    slack_dnd: bool,
+    #[serde(default)]
+    github_busy: bool,
+    #[serde(default)]
+    #[allow(dead_code)]
+    asana_dnd: bool, // Asana API doesn't support setting OOO yet
+    /// What the status is for, shown by `st list`
+    #[serde(default)]
+    description: Option<Cow<'static, str>>,
+    /// Back time when a date is given without one (e.g. "9am" for eod)
+    #[serde(default)]
+    default_back_time: Option<Cow<'static, str>>,
+    /// Whole status text when a back date is shown, e.g. "{status} until {date}"
+    #[serde(default)]
+    back_template: Option<Cow<'static, str>>,
+}
+
+const STATUSES: &[Status] = &[
+    Status {
+        keyword: Cow::Borrowed("lunch"),
+        slack_text: Cow::Borrowed("Lunchin'"),
+        slack_emoji: Cow::Borrowed(":fork_and_knife:"),
+        slack_dnd: true,
+        github_busy: false,
+        asana_dnd: false,
+        description: None,
+        default_back_time: None,
+        back_template: None,
+    },
+    Status {
+        keyword: Cow::Borrowed("zoom"),
+        slack_text: Cow::Borrowed("In a meeting (Zoom)"),
+        slack_emoji: Cow::Borrowed(":video_camera:"),
+        slack_dnd: false,
+        github_busy: false,
+        asana_dnd: false,
+        description: None,
+        default_back_time: None,
+        back_template: None,
+    },
+    Status {
+        keyword: Cow::Borrowed("tuple"),
+        slack_text: Cow::Borrowed("Pairing (Tuple)"),
+        slack_emoji: Cow::Borrowed(":couple:"),
+        slack_dnd: false,
+        github_busy: false,
+        asana_dnd: false,
+        description: None,
+        default_back_time: None,
+        back_template: None,
+    },
+    Status {
+        keyword: Cow::Borrowed("meet"),
+        slack_text: Cow::Borrowed("In a meeting"),
+        slack_emoji: Cow::Borrowed(":calendar:"),
+        slack_dnd: false,
+        github_busy: false,
+        asana_dnd: false,
+        description: None,
+        default_back_time: None,
+        back_template: None,
+    },
     Status {
-        keyword: "lunch",
-        slack_text: "Lunchin'",
-        slack_emoji: ":fork_and_knife:",
+        keyword: Cow::Borrowed("focus"),
+        slack_text: Cow::Borrowed("Heads down"),
+        slack_emoji: Cow::Borrowed(":headphones:"),
         slack_dnd: true,
         github_busy: false,
         asana_dnd: false,
+        description: None,
+        default_back_time: None,
+        back_template: None,
     },
     Status {
-        keyword: "zoom",
-        slack_text: "In a meeting (Zoom)",
-        slack_emoji: ":video_camera:",
-        slack_dnd: false,
+        keyword: Cow::Borrowed("commute"),
+        slack_text: Cow::Borrowed("Commuting"),
+        slack_emoji: Cow::Borrowed(":bus:"),
+        slack_dnd: true,
         github_busy: false,
         asana_dnd: false,
+        description: None,
+        default_back_time: None,
+        back_template: None,
     },
     Status {
-        keyword: "tuple",
-        slack_text: "Pairing (Tuple)",
-        slack_emoji: ":couple:",
-        slack_dnd: false,
+        keyword: Cow::Borrowed("appointment"),
+        slack_text: Cow::Borrowed("At an appointment"),
+        slack_emoji: Cow::Borrowed(":hospital:"),
+        slack_dnd: true,
         github_busy: false,
         asana_dnd: false,
+        description: None,
+        default_back_time: None,
+        back_template: None,
     },
     Status {
-        keyword: "meet",
-        slack_text: "In a meeting",
-        slack_emoji: ":calendar:",
+        keyword: Cow::Borrowed("conference"),
+        slack_text: Cow::Borrowed("At a conference"),
+        slack_emoji: Cow::Borrowed(":microphone:"),
         slack_dnd: false,
-        github_busy: false,
+        github_busy: true,
         asana_dnd: false,
+        description: None,
+        default_back_time: None,
+        back_template: None,
     },
     Status {
-        keyword: "eod",
-        slack_text: "Done for the day",
-        slack_emoji: ":wave:",
+        keyword: Cow::Borrowed("eod"),
+        slack_text: Cow::Borrowed("Done for the day"),
+        slack_emoji: Cow::Borrowed(":wave:"),
         slack_dnd: true,
         github_busy: false,
         asana_dnd: true,
+        description: None,
+        default_back_time: None,
+        back_template: None,
     },
     Status {
-        keyword: "vacation",
-        slack_text: "Vacation",
-        slack_emoji: ":desert_island:",
+        keyword: Cow::Borrowed("vacation"),
+        slack_text: Cow::Borrowed("Vacation"),
+        slack_emoji: Cow::Borrowed(":desert_island:"),
         slack_dnd: true,
         github_busy: true,
         asana_dnd: true,
+        description: None,
+        default_back_time: None,
+        back_template: None,
     },
     Status {
-        keyword: "sick",
-        slack_text: "Out sick",
-        slack_emoji: ":face_with_thermometer:",
+        keyword: Cow::Borrowed("sick"),
+        slack_text: Cow::Borrowed("Out sick"),
+        slack_emoji: Cow::Borrowed(":face_with_thermometer:"),
         slack_dnd: true,
         github_busy: false,
         asana_dnd: true,
+        description: None,
+        default_back_time: None,
+        back_template: None,
     },
     Status {
-        keyword: "away",
-        slack_text: "Out of office",
-        slack_emoji: ":no_entry:",
+        keyword: Cow::Borrowed("away"),
+        slack_text: Cow::Borrowed("Out of office"),
+        slack_emoji: Cow::Borrowed(":no_entry:"),
         slack_dnd: true,
         github_busy: true,
         asana_dnd: true,
+        description: None,
+        default_back_time: None,
+        back_template: None,
     },
     Status {
-        keyword: "back",
-        slack_text: "Catching up",
-        slack_emoji: ":inbox_tray:",
+        keyword: Cow::Borrowed("back"),
+        slack_text: Cow::Borrowed("Catching up"),
+        slack_emoji: Cow::Borrowed(":inbox_tray:"),
         slack_dnd: false,
         github_busy: false,
         asana_dnd: false,
+        description: None,
+        default_back_time: None,
+        back_template: None,
     },
 ];
 
-fn find_status(keyword: &str) -> Option<&'static Status> {
-    STATUSES.iter().find(|s| s.keyword == keyword)
+/// User-defined statuses from config take precedence over the built-ins.
+fn find_status<'a>(config: &'a Config, keyword: &str) -> Option<&'a Status> {
+    config
+        .statuses
+        .iter()
+        .chain(STATUSES.iter())
+        .find(|s| s.keyword == keyword)
+}
+
+fn available_keywords(config: &Config) -> Vec<&str> {
+    let mut keywords: Vec<&str> = STATUSES.iter().map(|s| s.keyword.as_ref()).collect();
+    for status in &config.statuses {
+        if !keywords.contains(&status.keyword.as_ref()) {
+            keywords.push(&status.keyword);
+        }
+    }
+    keywords.extend(COMMAND_KEYWORDS);
+    keywords
+}
+
+/// Keywords that run a command rather than set one of the statuses above.
+const COMMAND_KEYWORDS: [&str; 6] = ["custom", "snooze", "clear", "show", "list", "config"];
+
+/// `st conference "RustConf" friday`: takes the conference name off the front
+/// of the arguments, moving the rest up. A first argument that reads as a back
+/// date ("st conference friday") isn't a name.
+fn take_conference_name(cli: &mut Cli, config: &Config, opts: &RunOptions) -> Option<String> {
+    let first = cli.back_date.as_deref()?.trim();
+    let today = opts.zone.today();
+    let is_back_date = looks_like_time(first, config)
+        || parse_duration(first).is_some()
+        || parse_date(first, today, config).is_ok()
+        || parse_date_range(first, today, config).is_some();
+    if is_back_date || first.is_empty() {
+        return None;
+    }
+    let name = cli.back_date.take().map(|name| name.trim().to_string());
+    cli.back_date = cli.back_time.take();
+    if !cli.custom_args.is_empty() {
+        cli.back_time = Some(cli.custom_args.remove(0));
+    }
+    name
+}
+
+/// Fallback emoji for `st custom` when none is given.
+const DEFAULT_CUSTOM_EMOJI: &str = ":speech_balloon:";
+
+/// A one-off status from `st custom "text" [:emoji:] [back_date] [back_time]`.
+/// Returns it with the back date and time that follow the text and emoji.
+fn custom_status(args: Vec<String>, github_busy: bool) -> Result<(Status, Option<String>, Option<String>)> {
+    let usage = "Usage: st custom \"text\" [:emoji:] [back_date] [back_time]";
+    let mut args = args.into_iter().peekable();
+    let text = args
+        .next()
+        .filter(|text| !text.trim().is_empty())
+        .with_context(|| format!("Missing status text\n{usage}"))?;
+    let emoji = args.next_if(|arg| is_emoji_code(arg));
+    let (back_date, back_time) = (args.next(), args.next());
+    if let Some(extra) = args.next() {
+        anyhow::bail!("Unexpected argument: {extra}\n{usage}");
+    }
+    let status = Status {
+        keyword: Cow::Borrowed("custom"),
+        slack_text: Cow::Owned(text.trim().to_string()),
+        slack_emoji: emoji.map_or(Cow::Borrowed(DEFAULT_CUSTOM_EMOJI), Cow::Owned),
+        slack_dnd: false,
+        github_busy,
+        asana_dnd: false,
+        description: None,
+        default_back_time: None,
+        back_template: None,
+    };
+    Ok((status, back_date, back_time))
+}
+
+/// Aliases that work without any config; `[aliases]` entries are checked first.
+const BUILTIN_ALIASES: [(&str, &str); 2] = [("ooo", "away"), ("pto", "vacation")];
+
+/// The keyword an alias stands for. Aliases never shadow a status's own keyword.
+fn resolve_alias<'a>(config: &'a Config, keyword: &str) -> Option<&'a str> {
+    if find_status(config, keyword).is_some() {
+        return None;
+    }
+    config
+        .aliases
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(keyword))
+        .map(|(_, target)| target.as_str())
+        .or_else(|| BUILTIN_ALIASES.iter().find(|(alias, _)| *alias == keyword).map(|(_, target)| *target))
+}
+
+/// Every alias and its keyword, built-ins first unless `[aliases]` replaces them.
+fn all_aliases(config: &Config) -> Vec<(&str, &str)> {
+    let mut aliases: Vec<(&str, &str)> = BUILTIN_ALIASES
+        .iter()
+        .copied()
+        .filter(|(alias, _)| !config.aliases.keys().any(|a| a.eq_ignore_ascii_case(alias)))
+        .filter(|(alias, _)| find_status(config, alias).is_none())
+        .collect();
+    aliases.extend(config.aliases.iter().map(|(alias, target)| (alias.as_str(), target.as_str())));
+    aliases
+}
+
+/// "ooo (away), pto (vacation)" for the unknown-keyword error and `st list`.
+fn alias_list(config: &Config) -> String {
+    all_aliases(config)
+        .iter()
+        .map(|(alias, target)| format!("{alias} ({target})"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// --- Dry run ---
+
+/// Returned when a service's API token isn't set, so it can be told apart from
+/// a request that failed.
+#[derive(Debug)]
+struct MissingToken {
+    var: String,
+    config_key: &'static str,
+}
+
+impl std::fmt::Display for MissingToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let file = config_path().file_name().map(|f| f.to_string_lossy().into_owned()).unwrap_or_default();
+        write!(f, "set {} env var or {} in {file}", self.var, self.config_key)
+    }
+}
+
+impl std::error::Error for MissingToken {}
+
+/// Reads an API token from the environment, falling back to config.toml. In
+/// dry-run mode a missing token is noted and an empty placeholder is returned so
+/// the request can still be shown.
+fn read_token(
+    var: &'static str,
+    config_key: &'static str,
+    config_value: Option<&str>,
+    dry_run: bool,
+) -> Result<String> {
+    let var = token_var(var);
+    if let Ok(token) = std::env::var(&var) {
+        return Ok(token);
+    }
+    if let Some(token) = config_value {
+        return Ok(token.to_string());
+    }
+    if dry_run {
+        print_dry_run_line(&format!("{var} not set"));
+        return Ok(String::new());
+    }
+    Err(MissingToken { var, config_key }.into())
+}
+
+/// Every Slack call `st` makes acts as the user, so a bot token (which Slack
+/// rejects with a bare "not_allowed_token_type") is caught up front.
+fn slack_token(config: &Config, dry_run: bool) -> Result<String> {
+    let token = read_token("SLACK_PAT", "slack_token", config.slack_token.as_deref(), dry_run)?;
+    if token.starts_with("xoxb-") {
+        anyhow::bail!(
+            "{} is a bot token (xoxb-...), but setting a status needs a user token (xoxp-...), \
+             the User OAuth Token on your Slack app's OAuth & Permissions page",
+            token_var("SLACK_PAT")
+        );
+    }
+    Ok(token)
+}
+
+fn github_token(config: &Config, dry_run: bool) -> Result<String> {
+    read_token("GITHUB_PAT", "github_token", config.github_token.as_deref(), dry_run)
+}
+
+fn asana_token(config: &Config) -> Result<String> {
+    read_token("ASANA_PAT", "asana_token", config.asana_token.as_deref(), false)
+}
+
+fn gcal_token(config: &Config, dry_run: bool) -> Result<String> {
+    read_token("GCAL_TOKEN", "gcal_token", config.gcal_token.as_deref(), dry_run)
+}
+
+/// Discord is opt-in, so unlike the other tokens a missing one isn't an error.
+fn discord_token(config: &Config) -> Option<String> {
+    std::env::var(token_var("DISCORD_TOKEN")).ok().or_else(|| config.discord_token.clone())
+}
+
+fn print_dry_run(url: &str, body: &impl std::fmt::Display) {
+    print_dry_run_method("POST", url, body);
+}
+
+fn print_dry_run_method(method: &str, url: &str, body: &impl std::fmt::Display) {
+    print_dry_run_line(&format!("{method} {url}"));
+    print_dry_run_line(&format!("  {body}"));
+}
+
+/// Set by `--json`: dry-run lines go to stderr, so stdout is only the JSON.
+static DRY_RUN_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+fn print_dry_run_line(line: &str) {
+    if DRY_RUN_TO_STDERR.load(Ordering::Relaxed) {
+        eprintln!("  [dry run] {line}");
+    } else {
+        println!("  [dry run] {line}");
+    }
+}
+
+// --- HTTP ---
+
+/// Set by `--verbose`: every request and raw response is logged to stderr.
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Logs a request in verbose mode, with the token in `Authorization` headers and
+/// `token` query params cut down to its last 4 characters.
+fn log_request(method: &str, url: &str, headers: &[(&str, &str)], body: &impl std::fmt::Display) {
+    if !VERBOSE.load(Ordering::Relaxed) {
+        return;
+    }
+    eprintln!("  [verbose] {method} {}", redact_url(url));
+    for (name, value) in headers {
+        let value = if name.eq_ignore_ascii_case("authorization") {
+            match value.split_once(' ') {
+                Some((scheme, token)) => format!("{scheme} {}", redact(token)),
+                None => redact(value),
+            }
+        } else {
+            value.to_string()
+        };
+        eprintln!("  [verbose]   {name}: {value}");
+    }
+    let body = body.to_string();
+    if !body.is_empty() {
+        eprintln!("  [verbose]   {body}");
+    }
+}
+
+/// Logs a raw response body in verbose mode and parses it as JSON.
+fn read_response<T: DeserializeOwned>(body: &str) -> Result<T> {
+    if VERBOSE.load(Ordering::Relaxed) {
+        eprintln!("  [verbose] response: {body}");
+    }
+    serde_json::from_str(body).with_context(|| format!("Could not parse response: {body}"))
+}
+
+fn redact(secret: &str) -> String {
+    let tail: String = secret.chars().rev().take(4).collect::<Vec<_>>().into_iter().rev().collect();
+    format!("...{tail}")
+}
+
+fn redact_url(url: &str) -> String {
+    let Some((base, query)) = url.split_once('?') else {
+        return url.to_string();
+    };
+    let params: Vec<String> = query
+        .split('&')
+        .map(|param| match param.split_once('=') {
+            Some((key, value)) if key.eq_ignore_ascii_case("token") => format!("{key}={}", redact(value)),
+            _ => param.to_string(),
+        })
+        .collect();
+    format!("{base}?{}", params.join("&"))
+}
+
+/// Runs a request, retrying connection failures and timeouts with exponential
+/// backoff (200ms, 400ms, 800ms, ...). HTTP error statuses are not retried.
+fn with_retries<T>(retries: u32, mut request: impl FnMut() -> Result<T, ureq::Error>) -> Result<T, ureq::Error> {
+    let mut delay = std::time::Duration::from_millis(200);
+    let mut attempt = 0;
+    loop {
+        match request() {
+            Err(e) if attempt < retries && is_transient(&e) => {
+                std::thread::sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn is_transient(e: &ureq::Error) -> bool {
+    matches!(
+        e,
+        ureq::Error::Io(_)
+            | ureq::Error::Timeout(_)
+            | ureq::Error::HostNotFound
+            | ureq::Error::ConnectionFailed
+            | ureq::Error::BodyStalled
+    )
+}
+
+// --- GitHub integration ---
+
+fn github_graphql(token: &str, body: &serde_json::Value, retries: u32, dry_run: bool) -> Result<serde_json::Value> {
+    if dry_run {
+        print_dry_run("https://api.github.com/graphql", body);
+        return Ok(serde_json::Value::Null);
+    }
+    Ok(github_graphql_with_scopes(token, body, retries)?.0)
+}
+
+/// Sends a GraphQL request and also returns the token's scopes from the
+/// `X-OAuth-Scopes` header. Fine-grained tokens don't send the header.
+fn github_graphql_with_scopes(
+    token: &str,
+    body: &serde_json::Value,
+    retries: u32,
+) -> Result<(serde_json::Value, Option<String>)> {
+    let auth = format!("Bearer {token}");
+    log_request(
+        "POST",
+        "https://api.github.com/graphql",
+        &[("Authorization", &auth), ("User-Agent", "st-cli")],
+        body,
+    );
+    let (text, scopes) = with_retries(retries, || {
+        let mut response = ureq::post("https://api.github.com/graphql")
+            .header("Authorization", &auth)
+            .header("User-Agent", "st-cli")
+            .send_json(body)?;
+        let scopes = response
+            .headers()
+            .get("X-OAuth-Scopes")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        Ok((response.body_mut().read_to_string()?, scopes))
+    })?;
+    let resp: serde_json::Value = read_response(&text)?;
+
+    if let Some(errors) = resp.get("errors") {
+        anyhow::bail!("GraphQL error: {errors}");
+    }
+
+    Ok((resp, scopes))
+}
+
+/// The scope check runs once per run, however many orgs get a status.
+static GITHUB_SCOPE_CHECK: std::sync::Once = std::sync::Once::new();
+
+/// Warns when a classic token lacks the `user` scope, which otherwise shows up
+/// as an opaque GraphQL error from the mutation.
+fn check_github_scopes(token: &str, retries: u32) {
+    GITHUB_SCOPE_CHECK.call_once(|| {
+        let body = serde_json::json!({ "query": "query { viewer { login } }" });
+        let Ok((_, Some(scopes))) = github_graphql_with_scopes(token, &body, retries) else {
+            return;
+        };
+        if !scopes.split(',').any(|scope| scope.trim() == "user") {
+            eprintln!(
+                "Warning: GITHUB_PAT is missing the user scope needed to set a status (has: {})\n\
+                 Add it at https://github.com/settings/tokens, or run: gh auth refresh -s user",
+                if scopes.trim().is_empty() { "none" } else { scopes.trim() }
+            );
+        }
+    });
+}
+
+/// Sets busy, visible only to `org_id`'s members when given.
+/// `back_date` fills in the message; the status ends at `expires`.
+fn set_github_status(
+    status: &Status,
+    back_date: Option<DateTime<Zone>>,
+    expires: Option<DateTime<Zone>>,
+    org_id: Option<&str>,
+    config: &Config,
+    opts: &RunOptions,
+    dry_run: bool,
+) -> Result<()> {
+    let token = github_token(config, dry_run)?;
+
+    if !status.github_busy {
+        return Ok(());
+    }
+
+    // JSON string literals are valid GraphQL ones, so quotes in a --message survive
+    let mut input = format!(
+        "message: {}, emoji: {}, limitedAvailability: true",
+        serde_json::Value::from(plain_status_text(status, back_date, config)),
+        serde_json::Value::from(status.slack_emoji.as_ref()),
+    );
+
+    if let Some(dt) = expires {
+        input.push_str(&format!(", expiresAt: \"{}\"", dt.to_utc().format("%Y-%m-%dT%H:%M:%SZ")));
+    }
+
+    if let Some(id) = org_id {
+        input.push_str(&format!(", organizationId: \"{}\"", id));
+    }
+
+    let query = format!(
+        "mutation {{ changeUserStatus(input: {{ {input} }}) {{ status {{ message }} }} }}"
+    );
+
+    if !dry_run && !opts.skip_scope_check {
+        check_github_scopes(&token, config.retries());
+    }
+    let body = serde_json::json!({ "query": query });
+    github_graphql(&token, &body, config.retries(), dry_run)?;
+
+    Ok(())
+}
+
+fn clear_github_status(config: &Config, dry_run: bool) -> Result<()> {
+    let token = github_token(config, dry_run)?;
+
+    let body: serde_json::Value = serde_json::from_str(
+        r#"{"query":"mutation { changeUserStatus(input: {}) { clientMutationId } }"}"#,
+    )?;
+
+    github_graphql(&token, &body, config.retries(), dry_run)?;
+
+    Ok(())
+}
+
+struct GitHubStatus {
+    message: String,
+    emoji: String,
+    expires_at: Option<DateTime<Zone>>,
+}
+
+fn get_github_status(config: &Config, opts: &RunOptions) -> Result<Option<GitHubStatus>> {
+    let token = github_token(config, false)?;
+
+    let body = serde_json::json!({
+        "query": "query { viewer { status { message emoji expiresAt } } }"
+    });
+    let resp = github_graphql(&token, &body, config.retries(), false)?;
+
+    let status = &resp["data"]["viewer"]["status"];
+    if status.is_null() {
+        return Ok(None);
+    }
+
+    let expires_at = status["expiresAt"]
+        .as_str()
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&opts.zone));
+
+    Ok(Some(GitHubStatus {
+        message: status["message"].as_str().unwrap_or_default().to_string(),
+        emoji: status["emoji"].as_str().unwrap_or_default().to_string(),
+        expires_at,
+    }))
+}
+
+// --- Slack integration ---
+
+/// Status text, with "Back Friday." appended for OOO statuses. The time is only
+/// spelled out when it isn't the usual back hour ("Back Friday 1pm."). A text
+/// with `{back}` or `{time}` says where the back date goes itself.
+fn slack_status_text(
+    status: &Status,
+    back_date: Option<DateTime<Zone>>,
+    show_back_in_text: bool,
+    config: &Config,
+) -> String {
+    let default_hour = config.default_back_hour.unwrap_or(DEFAULT_BACK_HOUR);
+    match (back_date, show_back_in_text) {
+        (Some(dt), true) if dt.hour() == default_hour && dt.minute() == 0 => {
+            status_back_text(status, dt, false, config)
+        }
+        (Some(dt), true) => status_back_text(status, dt, true, config),
+        _ => plain_status_text(status, back_date, config),
+    }
+}
+
+/// Sends a Slack request and reads the body. A 429 is retried once after the
+/// `Retry-After` wait; a second one fails with how long Slack wants to wait.
+fn slack_request(
+    retries: u32,
+    mut request: impl FnMut() -> Result<ureq::http::Response<ureq::Body>, ureq::Error>,
+) -> Result<String> {
+    let mut waited = false;
+    loop {
+        let mut response = with_retries(retries, &mut request)?;
+        let status = response.status();
+        if status == ureq::http::StatusCode::TOO_MANY_REQUESTS {
+            let wait = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .unwrap_or(DEFAULT_SLACK_RETRY_AFTER_SECS);
+            if waited {
+                anyhow::bail!("Slack rate limited, retry in {wait}s");
+            }
+            eprintln!("Note: Slack rate limited, retrying in {wait}s");
+            std::thread::sleep(std::time::Duration::from_secs(wait));
+            waited = true;
+            continue;
+        }
+        if status.is_client_error() || status.is_server_error() {
+            return Err(ureq::Error::StatusCode(status.as_u16()).into());
+        }
+        return Ok(response.body_mut().read_to_string()?);
+    }
+}
+
+/// Like GitHub's, the Slack scope check runs once per run.
+static SLACK_SCOPE_CHECK: std::sync::Once = std::sync::Once::new();
+
+/// Warns when the Slack token lacks a scope the status needs, going by the
+/// `X-OAuth-Scopes` header on `auth.test`. A failed check is skipped.
+fn check_slack_scopes(token: &str, dnd: bool, retries: u32) {
+    SLACK_SCOPE_CHECK.call_once(|| {
+        let url = "https://slack.com/api/auth.test";
+        let auth = format!("Bearer {token}");
+        log_request("POST", url, &[("Authorization", &auth)], &"");
+        let Ok(Some(scopes)) = with_retries(retries, || {
+            let response = ureq::post(url).header("Authorization", &auth).send_empty()?;
+            Ok(response
+                .headers()
+                .get("X-OAuth-Scopes")
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string))
+        }) else {
+            return;
+        };
+        let needed = if dnd { &["users.profile:write", "dnd:write"][..] } else { &["users.profile:write"] };
+        let missing: Vec<&str> = needed
+            .iter()
+            .copied()
+            .filter(|needed| !scopes.split(',').any(|scope| scope.trim() == *needed))
+            .collect();
+        if !missing.is_empty() {
+            eprintln!(
+                "Warning: {} is missing the {} scope needed to set a status (has: {})\n\
+                 Add it under User Token Scopes on your Slack app's OAuth & Permissions page",
+                token_var("SLACK_PAT"),
+                missing.join(" and "),
+                if scopes.trim().is_empty() { "none" } else { scopes.trim() }
+            );
+        }
+    });
+}
+
+fn slack_expiration(back_date: Option<DateTime<Zone>>) -> i64 {
+    match back_date {
+        Some(dt) => dt.timestamp(),
+        None => 0,
+    }
+}
+
+/// `back_date` goes in the text; the status and DND end at `expires`.
+fn set_slack_status(
+    status: &Status,
+    back_date: Option<DateTime<Zone>>,
+    expires: Option<DateTime<Zone>>,
+    show_back_in_text: bool,
+    config: &Config,
+    opts: &RunOptions,
+    dry_run: bool,
+) -> Result<()> {
+    let token = slack_token(config, dry_run)?;
+
+    // A past back date would mean a full day of DND, and is far more often a typo
+    if status.slack_dnd
+        && !opts.allow_past
+        && let Some(dt) = expires.filter(|dt| *dt <= Utc::now())
+    {
+        anyhow::bail!(
+            "Back date is in the past ({} {}), not setting DND",
+            dt.format("%a %b %-d"),
+            format_time(dt, config)
+        );
+    }
+
+    if !dry_run && !opts.skip_scope_check {
+        check_slack_scopes(&token, status.slack_dnd, config.retries());
+    }
+    if !dry_run && !opts.skip_emoji_check {
+        check_slack_emoji(&token, &status.slack_emoji, config, opts);
+    }
+
+    let text = slack_status_text(status, back_date, show_back_in_text, config);
+    let expiration = slack_expiration(expires);
+
+    let profile = serde_json::json!({
+        "profile": {
+            "status_text": text,
+            "status_emoji": status.slack_emoji,
+            "status_expiration": expiration
+        }
+    });
+
+    if dry_run {
+        print_dry_run("https://slack.com/api/users.profile.set", &profile);
+    } else {
+        let auth = format!("Bearer {token}");
+        log_request(
+            "POST",
+            "https://slack.com/api/users.profile.set",
+            &[("Authorization", &auth)],
+            &profile,
+        );
+        let text = slack_request(config.retries(), || {
+            ureq::post("https://slack.com/api/users.profile.set")
+                .config()
+                .http_status_as_error(false)
+                .build()
+                .header("Authorization", &auth)
+                .send_json(&profile)
+        })?;
+        let resp: SlackResponse = read_response(&text)?;
+
+        if !resp.ok {
+            anyhow::bail!("Slack users.profile.set: {}", resp.error.unwrap_or_default());
+        }
+    }
+
+    if status.slack_dnd {
+        let minutes = match (expires, opts.forced_dnd_minutes) {
+            (None, Some(minutes)) => minutes,
+            _ => dnd_minutes(expires, Utc::now()),
+        };
+        set_slack_dnd(&token, minutes, config.retries(), dry_run)?;
+    }
+
+    Ok(())
+}
+
+/// Snooze length that ends DND at `expires` (rounded up to the minute, so it
+/// never lifts early), or a full day with no expiration. An expiration that
+/// has already passed (with `--allow-past`) gets a single minute, never a day.
+fn dnd_minutes(expires: Option<DateTime<Zone>>, now: DateTime<Utc>) -> i64 {
+    match expires {
+        Some(dt) => {
+            let seconds = dt.signed_duration_since(now).num_seconds();
+            ((seconds + 59) / 60).max(1)
+        }
+        None => 1440,
+    }
+}
+
+/// The emoji names Slack knows, standard and custom, looked up for this run.
+static SLACK_EMOJI: OnceLock<EmojiCache> = OnceLock::new();
+
+/// Slack's emoji names as of `date` (YYYY-MM-DD), kept in state.json for the day.
+#[derive(Serialize, Deserialize, Clone)]
+struct EmojiCache {
+    date: String,
+    names: Vec<String>,
+}
+
+/// Warns when Slack doesn't know a status's `:name:` emoji, which it would
+/// otherwise drop without a word. The built-in statuses' emoji aren't checked,
+/// and a failed lookup (e.g. no `emoji:read` scope) skips the check.
+fn check_slack_emoji(token: &str, emoji: &str, config: &Config, opts: &RunOptions) {
+    if !is_emoji_code(emoji) || STATUSES.iter().any(|s| s.slack_emoji == emoji) {
+        return;
+    }
+    let name = emoji.trim_matches(':');
+    if let Some(names) = slack_emoji_names(token, config, opts)
+        && !names.names.iter().any(|known| known == name)
+    {
+        eprintln!("Warning: Slack doesn't know the emoji {emoji}, so the status will show without one");
+    }
+}
+
+/// Today's cached emoji names, or a fresh `emoji.list` saved to state.json.
+fn slack_emoji_names(token: &str, config: &Config, opts: &RunOptions) -> Option<&'static EmojiCache> {
+    if let Some(cache) = SLACK_EMOJI.get() {
+        return Some(cache);
+    }
+    let today = opts.zone.today().format("%Y-%m-%d").to_string();
+    if let Some(cache) = read_state().and_then(|state| state.slack_emoji).filter(|c| c.date == today) {
+        return Some(SLACK_EMOJI.get_or_init(|| cache));
+    }
+
+    let url = "https://slack.com/api/emoji.list?include_categories=true";
+    let auth = format!("Bearer {token}");
+    log_request("GET", url, &[("Authorization", &auth)], &"");
+    let text = slack_request(config.retries(), || {
+        ureq::get(url)
+            .config()
+            .http_status_as_error(false)
+            .build()
+            .header("Authorization", &auth)
+            .call()
+    })
+    .ok()?;
+    let resp: SlackEmojiListResponse = read_response(&text).ok()?;
+    if !resp.ok {
+        return None;
+    }
+
+    let names = resp
+        .emoji
+        .into_keys()
+        .chain(resp.categories.into_iter().flat_map(|c| c.emoji_names))
+        .collect();
+    let cache = SLACK_EMOJI.get_or_init(|| EmojiCache { date: today, names });
+    let mut state = read_state().unwrap_or_default();
+    state.slack_emoji = Some(cache.clone());
+    write_state(&state);
+    Some(cache)
+}
+
+fn set_slack_dnd(token: &str, minutes: i64, retries: u32, dry_run: bool) -> Result<()> {
+    if dry_run {
+        print_dry_run("https://slack.com/api/dnd.setSnooze", &format!("num_minutes={minutes}"));
+        return Ok(());
+    }
+
+    let auth = format!("Bearer {token}");
+    let content_type = "application/x-www-form-urlencoded";
+    log_request(
+        "POST",
+        "https://slack.com/api/dnd.setSnooze",
+        &[("Authorization", &auth), ("Content-Type", content_type)],
+        &format!("num_minutes={minutes}"),
+    );
+    let text = slack_request(retries, || {
+        ureq::post("https://slack.com/api/dnd.setSnooze")
+            .config()
+            .http_status_as_error(false)
+            .build()
+            .header("Authorization", &auth)
+            .header("Content-Type", content_type)
+            .send_form([("num_minutes", &minutes.to_string())])
+    })?;
+    let resp: SlackResponse = read_response(&text)?;
+
+    if !resp.ok {
+        anyhow::bail!("Slack dnd.setSnooze: {}", resp.error.unwrap_or_default());
+    }
+
+    Ok(())
+}
+
+fn end_slack_dnd(token: &str, retries: u32, dry_run: bool) -> Result<()> {
+    if dry_run {
+        print_dry_run("https://slack.com/api/dnd.endSnooze", &"");
+        return Ok(());
+    }
+
+    let auth = format!("Bearer {token}");
+    let content_type = "application/x-www-form-urlencoded";
+    log_request(
+        "POST",
+        "https://slack.com/api/dnd.endSnooze",
+        &[("Authorization", &auth), ("Content-Type", content_type)],
+        &"",
+    );
+    let text = slack_request(retries, || {
+        ureq::post("https://slack.com/api/dnd.endSnooze")
+            .config()
+            .http_status_as_error(false)
+            .build()
+            .header("Authorization", &auth)
+            .header("Content-Type", content_type)
+            .send_form(std::iter::empty::<(&str, &str)>())
+    })?;
+    let resp: SlackResponse = read_response(&text)?;
+
+    // dnd.endSnooze returns ok=false with "snooze_not_active" if DND isn't on, which is fine
+    if !resp.ok && resp.error.as_deref() != Some("snooze_not_active") {
+        anyhow::bail!("Slack dnd.endSnooze: {}", resp.error.unwrap_or_default());
+    }
+
+    Ok(())
+}
+
+/// The `ooo_channel` message: `{keyword}` is the status keyword, and `{back}`
+/// and `{time}` are filled in (or dropped) as in a status text.
+fn ooo_message(status: &Status, back_date: Option<DateTime<Zone>>, config: &Config) -> String {
+    let template = config.ooo_message_template.as_deref().unwrap_or(DEFAULT_OOO_MESSAGE_TEMPLATE);
+    let text = template.replace("{keyword}", &status.keyword);
+    expand_status_text(&text, back_date, config).unwrap_or(text)
+}
+
+/// Sent once, without retries: a connection that drops after Slack has taken
+/// the message would otherwise post it twice.
+fn post_slack_message(token: &str, channel: &str, text: &str, dry_run: bool) -> Result<()> {
+    let body = serde_json::json!({ "channel": channel, "text": text });
+    if dry_run {
+        print_dry_run("https://slack.com/api/chat.postMessage", &body);
+        return Ok(());
+    }
+
+    let auth = format!("Bearer {token}");
+    log_request("POST", "https://slack.com/api/chat.postMessage", &[("Authorization", &auth)], &body);
+    let text = slack_request(0, || {
+        ureq::post("https://slack.com/api/chat.postMessage")
+            .config()
+            .http_status_as_error(false)
+            .build()
+            .header("Authorization", &auth)
+            .send_json(&body)
+    })?;
+    let resp: SlackResponse = read_response(&text)?;
+
+    if !resp.ok {
+        anyhow::bail!("Slack chat.postMessage: {}", resp.error.unwrap_or_default());
+    }
+
+    Ok(())
+}
+
+/// Puts back the Slack status that was there before `st` first set one.
+fn restore_slack_status(previous: &SlackProfile, config: &Config, dry_run: bool) -> Result<()> {
+    let token = slack_token(config, dry_run)?;
+    set_slack_profile(&token, &serde_json::json!({ "profile": previous }), config.retries(), dry_run)
+}
+
+fn set_slack_profile(token: &str, profile: &serde_json::Value, retries: u32, dry_run: bool) -> Result<()> {
+    if dry_run {
+        print_dry_run("https://slack.com/api/users.profile.set", profile);
+        return Ok(());
+    }
+
+    let auth = format!("Bearer {token}");
+    log_request(
+        "POST",
+        "https://slack.com/api/users.profile.set",
+        &[("Authorization", &auth)],
+        profile,
+    );
+    let text = slack_request(retries, || {
+        ureq::post("https://slack.com/api/users.profile.set")
+            .config()
+            .http_status_as_error(false)
+            .build()
+            .header("Authorization", &auth)
+            .send_json(profile)
+    })?;
+    let resp: SlackResponse = read_response(&text)?;
+
+    if !resp.ok {
+        anyhow::bail!("Slack users.profile.set: {}", resp.error.unwrap_or_default());
+    }
+
+    Ok(())
+}
+
+fn clear_slack_status(config: &Config, dry_run: bool) -> Result<()> {
+    let token = slack_token(config, dry_run)?;
+
+    let profile = serde_json::json!({
+        "profile": {
+            "status_text": "",
+            "status_emoji": "",
+            "status_expiration": 0
+        }
+    });
+
+    set_slack_profile(&token, &profile, config.retries(), dry_run)?;
+    end_slack_dnd(&token, config.retries(), dry_run)?;
+
+    Ok(())
+}
+
+fn get_slack_status(config: &Config) -> Result<SlackProfile> {
+    let token = slack_token(config, false)?;
+
+    let auth = format!("Bearer {token}");
+    log_request("GET", "https://slack.com/api/users.profile.get", &[("Authorization", &auth)], &"");
+    let text = slack_request(config.retries(), || {
+        ureq::get("https://slack.com/api/users.profile.get")
+            .config()
+            .http_status_as_error(false)
+            .build()
+            .header("Authorization", &auth)
+            .call()
+    })?;
+    let resp: SlackProfileGetResponse = read_response(&text)?;
+
+    if !resp.ok {
+        anyhow::bail!("Slack users.profile.get: {}", resp.error.unwrap_or_default());
+    }
+
+    resp.profile.context("Slack users.profile.get: missing profile")
+}
+
+#[derive(Deserialize)]
+struct SlackResponse {
+    ok: bool,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SlackEmojiListResponse {
+    ok: bool,
+    /// Custom emoji, by name
+    #[serde(default)]
+    emoji: BTreeMap<String, String>,
+    /// Standard emoji, with `include_categories`
+    #[serde(default)]
+    categories: Vec<SlackEmojiCategory>,
+}
+
+#[derive(Deserialize)]
+struct SlackEmojiCategory {
+    emoji_names: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct SlackProfileGetResponse {
+    ok: bool,
+    error: Option<String>,
+    profile: Option<SlackProfile>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct SlackProfile {
+    #[serde(default)]
+    status_text: String,
+    #[serde(default)]
+    status_emoji: String,
+    #[serde(default)]
+    status_expiration: i64,
+}
+
+// --- Asana (no API for setting OOO — can only read vacation_dates) ---
+
+#[derive(Deserialize)]
+struct AsanaResponse {
+    data: Vec<AsanaWorkspaceMembership>,
+}
+
+#[derive(Deserialize)]
+struct AsanaWorkspaceMembership {
+    workspace: Option<AsanaWorkspace>,
+    vacation_dates: Option<AsanaVacationDates>,
+}
+
+#[derive(Deserialize)]
+struct AsanaWorkspace {
+    gid: String,
+}
+
+#[derive(Deserialize)]
+struct AsanaUserResponse {
+    data: AsanaUser,
+}
+
+#[derive(Deserialize)]
+struct AsanaUser {
+    gid: String,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct AsanaVacationDates {
+    start_on: Option<String>,
+    end_on: Option<String>,
+}
+
+/// The Asana user gid looked up during this run, for `write_state` to keep.
+static ASANA_USER_GID: OnceLock<String> = OnceLock::new();
+
+/// `asana_user_gid` from config if set; otherwise the gid cached in state.json,
+/// or looked up from `asana_email` (or the token's own user) and cached there.
+fn resolve_asana_user_gid(config: &Config, token: &str) -> Result<String> {
+    if let Some(gid) = &config.asana_user_gid {
+        return Ok(gid.clone());
+    }
+    if let Some(gid) = ASANA_USER_GID.get().cloned().or_else(cached_asana_user_gid) {
+        return Ok(gid);
+    }
+
+    // Asana takes an email or "me" wherever it takes a user gid
+    let user = config.asana_email.as_deref().unwrap_or("me");
+    let url = format!("https://app.asana.com/api/1.0/users/{user}?opt_fields=gid");
+    let auth = format!("Bearer {token}");
+    log_request("GET", &url, &[("Authorization", &auth)], &"");
+    let text = with_retries(config.retries(), || {
+        ureq::get(&url)
+            .header("Authorization", &auth)
+            .call()?
+            .into_body()
+            .read_to_string()
+    })
+    .with_context(|| format!("Could not look up Asana user {user}"))?;
+    let resp: AsanaUserResponse = read_response(&text)?;
+
+    let gid = ASANA_USER_GID.get_or_init(|| resp.data.gid).clone();
+    let mut state = read_state().unwrap_or_default();
+    state.asana_user_gid = Some(gid.clone());
+    write_state(&state);
+    Ok(gid)
+}
+
+fn asana_ooo_is_set(config: &Config) -> Result<bool> {
+    let token = asana_token(config)?;
+    let user_gid = resolve_asana_user_gid(config, &token)?;
+
+    let url = format!(
+        "https://app.asana.com/api/1.0/users/{user_gid}/workspace_memberships?opt_fields=workspace,vacation_dates"
+    );
+
+    let auth = format!("Bearer {token}");
+    log_request("GET", &url, &[("Authorization", &auth)], &"");
+    let text = with_retries(config.retries(), || {
+        ureq::get(&url)
+            .header("Authorization", &auth)
+            .call()?
+            .into_body()
+            .read_to_string()
+    })?;
+    let resp: AsanaResponse = read_response(&text)?;
+
+    // Limit to one workspace when configured, so a personal workspace's OOO doesn't count
+    let in_workspace = |m: &&AsanaWorkspaceMembership| match config.asana_workspace_gid.as_deref() {
+        Some(gid) => m.workspace.as_ref().is_some_and(|w| w.gid == gid),
+        None => true,
+    };
+
+    Ok(resp
+        .data
+        .iter()
+        .filter(in_workspace)
+        .any(|m| m.vacation_dates.is_some()))
+}
+
+fn asana_ooo_summary(config: &Config) -> Option<String> {
+    match asana_ooo_is_set(config) {
+        Ok(true) => Some("OOO is set".into()),
+        Ok(false) => None,
+        Err(_) => None,
+    }
+}
+
+// --- State ---
+
+/// What `st` last set, so `st back` and `st show` know what it did.
+#[derive(Serialize, Deserialize, Default)]
+struct State {
+    /// Empty when nothing has been set and the state only holds lookups
+    keyword: String,
+    /// Unix timestamps
+    set_at: i64,
+    back_date: Option<i64>,
+    /// The GitHub status was set by `st`, so it's `st`'s to clear
+    github_busy: bool,
+    /// Looked up from `asana_email` (or the token's user) when `asana_user_gid` isn't set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    asana_user_gid: Option<String>,
+    /// The Slack status from before `st` set one, for `st back` to put back
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    previous_slack: Option<SlackProfile>,
+    /// Slack's emoji names, looked up at most once a day for the emoji check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    slack_emoji: Option<EmojiCache>,
+}
+
+impl State {
+    fn has_status(&self) -> bool {
+        !self.keyword.is_empty()
+    }
+}
+
+fn state_path() -> PathBuf {
+    profile_file("state.json")
+}
+
+/// A missing state file is normal; a corrupt one is reported and ignored.
+fn read_state() -> Option<State> {
+    let path = state_path();
+    let contents = std::fs::read_to_string(&path).ok()?;
+    match serde_json::from_str(&contents) {
+        Ok(state) => Some(state),
+        Err(e) => {
+            eprintln!("Warning: ignoring unreadable {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+fn cached_asana_user_gid() -> Option<String> {
+    read_state().and_then(|state| state.asana_user_gid)
+}
+
+fn write_state(state: &State) {
+    let path = state_path();
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::write(&path, serde_json::to_string(state).unwrap_or_default()));
+    if let Err(e) = result {
+        eprintln!("Warning: could not save {}: {e}", path.display());
+    }
+}
+
+fn clear_state() {
+    let _ = std::fs::remove_file(state_path());
+}
+
+// --- CLI ---
+
+#[derive(Parser)]
+#[command(name = "st", about = "Set your status across services", version)]
+struct Cli {
+    /// Status keyword: lunch, zoom, tuple, meet, focus, commute, appointment, eod, vacation, sick, away, conference, back, custom, snooze, clear, show, list, config.
+    /// Leave it out to pick one from a menu.
+    keyword: Option<String>,
+
+    /// Back date: when you'll return (e.g., friday, 2/28, 2026-03-10, tomorrow)
+    back_date: Option<String>,
+
+    /// Back time: what time you'll return (e.g., 8am, 9:30am, 15:00, noon, eob). Defaults to 7am or default_back_hour.
+    /// A second date instead (st vacation 3/10 3/14) makes the first the start of a leave range.
+    back_time: Option<String>,
+
+    /// The rest of `st custom "text" [:emoji:] [back_date] [back_time]`
+    #[arg(hide = true)]
+    custom_args: Vec<String>,
+
+    /// Status text to use in place of the keyword's, for this time only
+    #[arg(short, long)]
+    message: Option<String>,
+
+    /// Emoji to use in place of the keyword's, for this time only (e.g., :palm_tree: or 🌴)
+    #[arg(long)]
+    emoji: Option<String>,
+
+    /// Set the status without turning on Slack DND
+    #[arg(long)]
+    no_dnd: bool,
+
+    /// Turn on Slack DND even if the status doesn't, until the back date (or for an hour)
+    #[arg(long, conflicts_with = "no_dnd")]
+    dnd: bool,
+
+    /// With `st custom`: also set limited availability on GitHub
+    #[arg(long)]
+    github_busy: bool,
+
+    /// Back after a duration from now instead of at a date (e.g., 2h, 45m, 1h30m)
+    #[arg(long = "for", value_name = "DURATION", conflicts_with_all = ["back_date", "back_time", "date", "time"])]
+    for_duration: Option<String>,
+
+    /// Back date, in place of the positional one (e.g., --date friday)
+    #[arg(long, value_name = "DATE")]
+    date: Option<String>,
+
+    /// Back time, in place of the positional one; for lunch, focus, and commute, the time or duration
+    #[arg(long, value_name = "TIME")]
+    time: Option<String>,
+
+    /// End the Slack status, DND, and GitHub status at this time (today, or tomorrow if past), whatever the back date
+    #[arg(long, value_name = "TIME")]
+    until: Option<String>,
+
+    /// Print the API requests that would be sent without sending them
+    #[arg(short = 'n', long)]
+    dry_run: bool,
+
+    /// Print the result as a single JSON object
+    #[arg(long)]
+    json: bool,
+
+    /// Only print errors
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// Allow a back date that has already passed
+    #[arg(long)]
+    allow_past: bool,
+
+    /// Don't ask before setting a back date more than confirm_threshold_days away
+    #[arg(short, long)]
+    yes: bool,
+
+    /// Don't check the GitHub and Slack tokens' scopes before setting a status
+    #[arg(long)]
+    skip_scope_check: bool,
+
+    /// Don't check that Slack knows a custom status's emoji before setting it
+    #[arg(long)]
+    skip_emoji_check: bool,
+
+    /// Move back dates that land on a weekend to Monday (same as skip_weekends in config)
+    #[arg(long)]
+    workdays: bool,
+
+    /// Retries after a connection failure or timeout (default 3)
+    #[arg(long)]
+    retries: Option<u32>,
+
+    /// Read ~/.config/st/NAME.toml instead of config.toml, and tokens from SLACK_PAT_NAME and so on
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// IANA time zone that back times are given in (e.g., America/Chicago)
+    #[arg(long)]
+    tz: Option<String>,
+
+    /// Color the report: auto (on a terminal, unless NO_COLOR is set), always, or never
+    #[arg(long, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Log every HTTP request and response to stderr (tokens redacted)
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+/// Moves `--date` and `--time` into the positional slots they stand for.
+/// Lunch, focus, and commute take their time (or duration) where others take
+/// the date, so `--time` goes there for them and `--date` doesn't apply.
+fn apply_date_time_flags(cli: &mut Cli, keyword: &str, config: &Config) -> Result<()> {
+    let both = |flag: &str, positional: &str| {
+        anyhow::anyhow!("{flag} and a positional {positional} were both given; use one or the other")
+    };
+    if matches!(keyword, "lunch" | "focus" | "commute") {
+        if cli.date.is_some() {
+            anyhow::bail!("st {keyword} doesn't take a date; give --time instead");
+        }
+        if let Some(time) = cli.time.take() {
+            if cli.back_date.is_some() {
+                return Err(both("--time", "time"));
+            }
+            cli.back_date = Some(time);
+        }
+        return Ok(());
+    }
+
+    if let Some(date) = cli.date.take() {
+        if cli.back_date.is_some() {
+            return Err(both("--date", "date"));
+        }
+        cli.back_date = Some(date);
+    }
+    if let Some(time) = cli.time.take() {
+        // A lone positional time ("st away 3pm") is already the time
+        let lone_time = cli.back_time.is_none() && cli.back_date.as_deref().is_some_and(|d| looks_like_time(d, config));
+        if cli.back_time.is_some() || lone_time {
+            return Err(both("--time", "time"));
+        }
+        match cli.back_date {
+            Some(_) => cli.back_time = Some(time),
+            None => cli.back_date = Some(time),
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+            }
+        }
+    }
+}
+
+/// Bare `st`: a numbered menu of statuses, then an optional back date ("friday",
+/// "tomorrow 3pm"). Returns the keyword, back date, and back time.
+fn pick_status(config: &Config) -> Result<(String, Option<String>, Option<String>)> {
+    let statuses: Vec<&Status> = available_keywords(config)
+        .into_iter()
+        .filter_map(|k| find_status(config, k))
+        .collect();
+    for (i, status) in statuses.iter().enumerate() {
+        eprintln!("  {:>2}. {:<10} {} {}", i + 1, status.keyword, status.slack_text, status.slack_emoji);
+    }
+
+    let read_line = |prompt: &str| -> Result<String> {
+        eprint!("{prompt}");
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line).context("Could not read input")?;
+        Ok(line.trim().to_string())
+    };
+
+    let choice = read_line(&format!("Status [1-{}]: ", statuses.len()))?;
+    let status = match choice.parse::<usize>() {
+        Ok(n) => statuses.get(n.wrapping_sub(1)).copied(),
+        Err(_) => find_status(config, &choice.to_lowercase()),
+    }
+    .with_context(|| format!("No status {choice:?}"))?;
+
+    let back = read_line("Back date (optional, e.g. friday or tomorrow 3pm): ")?;
+    let (back_date, back_time) = match back.rsplit_once(' ') {
+        _ if back.is_empty() => (None, None),
+        Some((date, time)) if looks_like_time(time, config) => (Some(date.to_string()), Some(time.to_string())),
+        _ => (Some(back), None),
+    };
+    Ok((status.keyword.to_string(), back_date, back_time))
+}
+
+fn main() {
+    let mut cli = Cli::parse();
+    if let Some(name) = &cli.profile
+        && let Err(e) = set_profile(name)
+    {
+        eprintln!("{e}");
+        std::process::exit(2);
+    }
+    if cli.keyword.as_deref().is_some_and(|k| k.eq_ignore_ascii_case("config")) {
+        if !cli.back_date.as_deref().is_some_and(|a| a.eq_ignore_ascii_case("check")) {
+            eprintln!("Usage: st config check");
+            std::process::exit(1);
+        }
+        if !run_config_check() {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let (mut config, mut opts) = load_config().unwrap_or_else(|e| {
+        eprintln!("{e:#}");
+        std::process::exit(1);
+    });
+    VERBOSE.store(cli.verbose, Ordering::Relaxed);
+    DRY_RUN_TO_STDERR.store(cli.json, Ordering::Relaxed);
+    if cli.workdays {
+        config.skip_weekends = true;
+    }
+    opts.allow_past = cli.allow_past;
+    opts.skip_scope_check = cli.skip_scope_check;
+    opts.skip_emoji_check = cli.skip_emoji_check;
+    opts.output = OutputMode {
+        json: cli.json,
+        quiet: cli.quiet,
+        color: !cli.json && cli.color.enabled(),
+    };
+    if cli.retries.is_some() {
+        config.retries = cli.retries;
+    }
+    if let Some(name) = &cli.tz {
+        opts.zone = Zone::parse(name).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        });
+    }
+    if cli.keyword.is_none() {
+        if !std::io::stdin().is_terminal() {
+            eprintln!("{}", Cli::command().render_usage());
+            std::process::exit(2);
+        }
+        let (keyword, back_date, back_time) = pick_status(&config).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        });
+        cli.keyword = Some(keyword);
+        cli.back_date = back_date;
+        cli.back_time = back_time;
+    }
+    let mut keyword = cli.keyword.as_deref().unwrap_or_default().to_lowercase();
+    if let Some(target) = resolve_alias(&config, &keyword) {
+        keyword = target.to_lowercase();
+    }
+    let is_clear = keyword == "clear";
+    let is_show = keyword == "show";
+    let is_list = keyword == "list";
+    let is_custom = keyword == "custom";
+    let is_back_now = keyword == "back"
+        && cli
+            .back_date
+            .as_deref()
+            .is_some_and(|d| d.eq_ignore_ascii_case("now"));
+
+    if let Err(e) = check_duplicate_statuses(&config) {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+
+    if keyword == "snooze" {
+        let snooze = parse_snooze(cli.back_date.as_deref()).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(2);
+        });
+        if cli.dry_run && !cli.json && !cli.quiet {
+            println!("Dry run: no changes will be made");
+        }
+        if !run_snooze(snooze, &config, &opts, cli.dry_run) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if !is_clear && !is_show && !is_list && !is_custom && find_status(&config, &keyword).is_none() {
+        eprintln!(
+            "Unknown keyword: {keyword}\nAvailable: {}\nAliases: {}\nRun `st list` to see what each status does",
+            available_keywords(&config).join(", "),
+            alias_list(&config)
+        );
+        std::process::exit(1);
+    }
+
+    if is_list {
+        run_list(&config, cli.json);
+        return;
+    }
+
+    if is_show {
+        run_show(&config, &opts);
+        return;
+    }
+
+    // "st custom" takes its text and emoji ahead of the back date and time
+    let custom = if is_custom {
+        let args = [cli.back_date.take(), cli.back_time.take()]
+            .into_iter()
+            .flatten()
+            .chain(std::mem::take(&mut cli.custom_args))
+            .collect();
+        let (status, back_date, back_time) =
+            custom_status(args, cli.github_busy).unwrap_or_else(|e| {
+                eprintln!("{e}");
+                std::process::exit(1);
+            });
+        cli.back_date = back_date;
+        cli.back_time = back_time;
+        Some(status)
+    } else {
+        let conference = if keyword == "conference" {
+            take_conference_name(&mut cli, &config, &opts)
+        } else {
+            None
+        };
+        if let Some(extra) = cli.custom_args.first() {
+            eprintln!("Unexpected argument: {extra}\nUsage: st <keyword> [back_date] [back_time]");
+            std::process::exit(2);
+        }
+        if cli.github_busy {
+            eprintln!("--github-busy only applies to st custom");
+            std::process::exit(2);
+        }
+        conference.and_then(|name| {
+            let mut status = find_status(&config, &keyword)?.clone();
+            status.slack_text = Cow::Owned(format!("At {name}"));
+            Some(status)
+        })
+    };
+    let mut status = custom.or_else(|| find_status(&config, &keyword).cloned());
+    if let Some(status) = &mut status
+        && let Some(message) = &cli.message
+    {
+        if message.trim().is_empty() {
+            eprintln!("--message can't be empty");
+            std::process::exit(2);
+        }
+        status.slack_text = Cow::Owned(message.trim().to_string());
+    }
+    if let Some(status) = &mut status
+        && let Some(emoji) = &cli.emoji
+    {
+        status.slack_emoji = Cow::Owned(parse_emoji_arg(emoji).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(2);
+        }));
+    }
+    if let Some(status) = &mut status
+        && cli.no_dnd
+    {
+        status.slack_dnd = false;
+    }
+    // A status without DND of its own has no long snooze in mind, so --dnd
+    // without a back date is an hour rather than a full day
+    let forced_dnd = cli.dnd && status.as_ref().is_some_and(|s| !s.slack_dnd);
+    if let Some(status) = &mut status
+        && cli.dnd
+    {
+        status.slack_dnd = true;
+    }
+    let status = status.as_ref();
+
+    if let Err(e) = apply_date_time_flags(&mut cli, &keyword, &config) {
+        eprintln!("{e}");
+        std::process::exit(2);
+    }
+
+    let is_meeting = matches!(keyword.as_str(), "zoom" | "meet" | "tuple");
+    let default_time = status.and_then(|s| s.default_back_time.as_deref());
+    let now = opts.zone.now();
+    let (leave_start, back_dt) = if is_back_now {
+        (None, None)
+    } else if let Some(duration) = &cli.for_duration {
+        let back = parse_back_duration(duration, now).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        });
+        (None, Some(back))
+    } else if keyword == "focus" {
+        let back = parse_focus_back_time(cli.back_date.as_deref(), now, &config, &opts).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        });
+        (None, Some(back))
+    } else if keyword == "commute" {
+        let back = parse_commute_back_time(cli.back_date.as_deref(), now, &config, &opts).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        });
+        (None, Some(back))
+    } else if keyword == "appointment" && cli.back_date.is_none() {
+        // An hour, rounded like lunch; "st appointment 3pm" takes the usual path
+        let round_to = config.lunch_round_to_minutes.unwrap_or(DEFAULT_LUNCH_ROUND_MINUTES);
+        (None, Some(default_lunch_back(now, round_to, DEFAULT_APPOINTMENT_MINUTES)))
+    } else if keyword == "lunch" {
+        let time = cli.back_date.as_deref(); // for lunch, second arg is a time
+        let back = parse_lunch_back_time(time, now, &config, &opts).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        });
+        (None, Some(back))
+    } else if let Some(minutes) = cli
+        .back_date
+        .as_deref()
+        .filter(|s| is_meeting && s.chars().all(|c| c.is_ascii_digit()))
+    {
+        // Meetings take bare minutes: "st zoom 30"
+        let back = parse_back_duration(&format!("{minutes}m"), now).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        });
+        (None, Some(back))
+    } else if let Some(s) = &cli.back_date {
+        // "st vacation 3/10 3/14": a second date in place of the time makes a range
+        let (date_str, time_str) = match cli.back_time.as_deref() {
+            Some(end) if !looks_like_time(end, &config) && parse_date(end, now.date_naive(), &config).is_ok() => {
+                (format!("{s} to {end}"), None)
+            }
+            time => (s.clone(), time),
+        };
+        let (start, back, note) =
+            parse_back_range(&date_str, time_str, default_time, now, &config, &opts).unwrap_or_else(|e| {
+                eprintln!("{e}");
+                std::process::exit(1);
+            });
+        if let Some(note) = note {
+            eprintln!("Note: {note}");
+        }
+        if let Some(start) = start.filter(|start| *start > now.date_naive()) {
+            eprintln!(
+                "Note: Slack and GitHub can't schedule a status, so it starts now rather than {} {}/{}",
+                start.format("%A"),
+                start.month(),
+                start.day()
+            );
+        }
+        (start, Some(back))
+    } else {
+        (None, None)
+    };
+
+    let until = cli.until.as_deref().map(|input| {
+        parse_until(input, now, &config, &opts).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        })
+    });
+    if forced_dnd && back_dt.is_none() && until.is_none() {
+        opts.forced_dnd_minutes = Some(DEFAULT_FORCED_DND_MINUTES);
+    }
+
+    if is_meeting
+        && let Some(back) = until.or(back_dt)
+        && back - opts.zone.now() > chrono::Duration::hours(12)
+    {
+        eprintln!("Warning: this meeting runs over 12 hours (until {})", format_until(back, &config));
+    }
+
+    if !cli.yes
+        && !cli.dry_run
+        && let Some(back) = until.or(back_dt)
+        && let Some(status) = status
+        && !confirm_far_back(status, back, &config, &opts)
+    {
+        eprintln!("Cancelled, nothing was changed");
+        std::process::exit(1);
+    }
+
+    if cli.dry_run && !cli.json && !cli.quiet {
+        println!("Dry run: no changes will be made");
+    }
+
+    let ok = if is_clear {
+        run_clear(&config, &opts, cli.dry_run)
+    } else if is_back_now {
+        run_back_now(&config, &opts, cli.dry_run)
+    } else {
+        run_set(status.unwrap(), back_dt, until, leave_start, &config, &opts, cli.dry_run)
+    };
+
+    if !ok {
+        std::process::exit(1);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Outcome {
+    Ok,
+    Error,
+    Skipped,
+    /// Nothing was changed, but the user needs to do something by hand
+    Action,
+}
+
+impl Outcome {
+    fn symbol(self) -> &'static str {
+        match self {
+            Outcome::Ok => "\u{2713}",
+            Outcome::Error => "\u{2717}",
+            Outcome::Skipped => "-",
+            Outcome::Action => "!",
+        }
+    }
+
+    /// The symbol in green, red, yellow, or dim, when color is on.
+    fn styled_symbol(self, color: bool) -> String {
+        let code = match self {
+            Outcome::Ok => "32",
+            Outcome::Error => "31",
+            Outcome::Action => "33",
+            Outcome::Skipped => "2",
+        };
+        if color {
+            format!("\x1b[{code}m{}\x1b[0m", self.symbol())
+        } else {
+            self.symbol().to_string()
+        }
+    }
+
+    fn json_status(self) -> &'static str {
+        match self {
+            Outcome::Ok => "ok",
+            Outcome::Error => "error",
+            Outcome::Skipped | Outcome::Action => "skipped",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct OutputMode {
+    json: bool,
+    /// Only report failures
+    quiet: bool,
+    /// Color the report symbols (never with `--json`)
+    color: bool,
+}
+
+/// One line of the per-service report, printed once every service has finished.
+struct Report {
+    outcome: Outcome,
+    detail: String,
+    /// Extra fields for `--json` output
+    fields: serde_json::Map<String, serde_json::Value>,
+    /// The error was a missing API token (see `ignore_missing_tokens`)
+    missing_token: bool,
+}
+
+impl Report {
+    fn new(outcome: Outcome, detail: impl Into<String>) -> Self {
+        Report {
+            outcome,
+            detail: detail.into(),
+            fields: serde_json::Map::new(),
+            missing_token: false,
+        }
+    }
+
+    fn error(e: anyhow::Error) -> Self {
+        Report {
+            missing_token: e.is::<MissingToken>(),
+            ..Report::new(Outcome::Error, e.to_string())
+        }
+    }
+
+    fn from_result(result: Result<()>, detail: impl Into<String>) -> Self {
+        match result {
+            Ok(()) => Report::new(Outcome::Ok, detail),
+            Err(e) => Report::error(e),
+        }
+    }
+}
+
+/// One service's update, named for its report lines.
+type Service<'a> = (&'static str, Box<dyn FnOnce() -> Vec<Report> + Send + 'a>);
+
+/// Runs the service updates concurrently and prints their reports in the
+/// order given. Dry runs stay sequential so each service's printed requests
+/// aren't interleaved. Quiet mode prints nothing when everything succeeded and
+/// only errors otherwise.
+fn run_services(config: &Config, dry_run: bool, output: OutputMode, services: Vec<Service>) -> ServiceResults {
+    let (names, updates): (Vec<_>, Vec<_>) = services.into_iter().unzip();
+    let mut reports: Vec<Vec<Report>> = if dry_run {
+        updates.into_iter().map(|update| update()).collect()
+    } else {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = updates.into_iter().map(|update| scope.spawn(update)).collect();
+            handles.into_iter().map(join_service).collect()
+        })
+    };
+
+    if config.ignore_missing_tokens {
+        for report in reports.iter_mut().flatten().filter(|r| r.missing_token) {
+            report.outcome = Outcome::Skipped;
+        }
+    }
+
+    let ok = !reports.iter().flatten().any(|r| r.outcome == Outcome::Error);
+    let mut json = serde_json::Map::new();
+    // A service with no reports isn't configured and is left out
+    for (name, reports) in names.iter().zip(&reports).filter(|(_, r)| !r.is_empty()) {
+        json.insert(name.to_lowercase(), service_json(reports));
+    }
+    let results = ServiceResults { ok, services: json };
+
+    if output.quiet && ok {
+        return results;
+    }
+
+    if output.json {
+        println!("{}", serde_json::Value::Object(results.services.clone()));
+        return results;
+    }
+
+    for (name, reports) in names.iter().zip(reports) {
+        for report in reports {
+            let line = format!("  {name:<8} {} {}", report.outcome.styled_symbol(output.color), report.detail);
+            if report.outcome == Outcome::Error {
+                eprintln!("{line}");
+            } else if !output.quiet {
+                println!("{line}");
+            }
+        }
+    }
+    results
+}
+
+/// What `run_services` did.
+struct ServiceResults {
+    /// No service failed
+    ok: bool,
+    /// Each service's result, as `--json` prints it
+    services: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Folds a service's reports into one JSON object: any error wins, details are
+/// joined, and extra fields are merged.
+fn service_json(reports: &[Report]) -> serde_json::Value {
+    let outcome = reports
+        .iter()
+        .map(|r| r.outcome)
+        .find(|o| *o == Outcome::Error)
+        .or_else(|| reports.last().map(|r| r.outcome))
+        .unwrap_or(Outcome::Skipped);
+
+    let mut obj = serde_json::Map::new();
+    obj.insert("status".into(), outcome.json_status().into());
+    let details: Vec<&str> = reports.iter().map(|r| r.detail.as_str()).collect();
+    obj.insert("detail".into(), details.join("; ").into());
+    for report in reports {
+        obj.extend(report.fields.clone());
+    }
+    serde_json::Value::Object(obj)
+}
+
+fn join_service(handle: std::thread::ScopedJoinHandle<'_, Vec<Report>>) -> Vec<Report> {
+    handle
+        .join()
+        .unwrap_or_else(|_| vec![Report::new(Outcome::Error, "panicked")])
+}
+
+/// Asks before setting a back date more than `confirm_threshold_days` out (a
+/// month of DND is easy to set by accident). Never asks without a terminal.
+fn confirm_far_back(status: &Status, back: DateTime<Zone>, config: &Config, opts: &RunOptions) -> bool {
+    let threshold = config.confirm_threshold_days.unwrap_or(DEFAULT_CONFIRM_THRESHOLD_DAYS);
+    let days = (back.date_naive() - opts.zone.today()).num_days();
+    if days <= i64::from(threshold) || !std::io::stdin().is_terminal() {
+        return true;
+    }
+
+    eprint!("{} That's {days} days away. Continue? [y/N] ", status_back_text(status, back, true, config));
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// `until` (from `--until`) replaces the back date as the expiration.
+fn run_set(
+    status: &Status,
+    back_date: Option<DateTime<Zone>>,
+    until: Option<DateTime<Zone>>,
+    leave_start: Option<NaiveDate>,
+    config: &Config,
+    opts: &RunOptions,
+    dry_run: bool,
+) -> bool {
+    let back_date = match back_date {
+        None if status.keyword == "eod" => default_eod_back(status, opts.zone.today(), config, opts),
+        back_date => back_date,
+    };
+    let expires = until.or(back_date);
+    // Busy on GitHub can last through the back day, so it's still up while catching up
+    let github_expires = match (until, back_date) {
+        (None, Some(back)) if config.github_expire_at_end_of_day => {
+            Some(to_local_datetime(back.date_naive(), NaiveTime::from_hms_opt(23, 59, 0).unwrap(), config, opts))
+        }
+        _ => expires,
+    };
+    // Suggested Asana OOO dates: first day out through the day before the back date
+    let ooo_dates = leave_start
+        .zip(back_date)
+        .map(|(start, back)| (start, back.date_naive() - chrono::Duration::days(1)));
+
+    // The Slack status from before st took over: kept across sets, and put back by
+    // "back". A "back" ends the run, so the next set looks the status up again,
+    // passing over a "Catching up" that's still showing.
+    let is_back = status.keyword == "back";
+    let state = read_state();
+    let catching_up = find_status(config, "back");
+    let previous_slack = match &state {
+        Some(state) if state.has_status() && state.keyword != "back" => state.previous_slack.clone(),
+        _ if !dry_run && !is_back => get_slack_status(config)
+            .ok()
+            .filter(|p| !p.status_text.is_empty() || !p.status_emoji.is_empty())
+            .filter(|p| {
+                !catching_up.is_some_and(|s| {
+                    p.status_text == slack_status_text(s, None, false, config) && p.status_emoji == s.slack_emoji
+                })
+            }),
+        _ => None,
+    };
+    let restore = previous_slack
+        .as_ref()
+        .filter(|p| is_back && (p.status_expiration == 0 || p.status_expiration > Utc::now().timestamp()));
+
+    let slack = || {
+        let mut reports = slack_set_report(status, back_date, expires, restore, config, opts, dry_run);
+        reports.extend(slack_ooo_post_report(status, back_date, config, dry_run));
+        reports
+    };
+    let services: Vec<Service> = vec![
+        ("Slack", Box::new(slack)),
+        ("GitHub", Box::new(|| github_set_report(status, back_date, github_expires, config, opts, dry_run))),
+        ("Asana", Box::new(|| asana_set_report(status, ooo_dates, config))),
+        (
+            "Calendar",
+            Box::new(|| calendar_set_report(status, back_date, leave_start, opts.zone.today(), config, opts, dry_run)),
+        ),
+        ("Discord", Box::new(|| discord_set_report(status, back_date, expires, config, dry_run))),
+    ];
+    let results = run_services(config, dry_run, opts.output, services);
+    if !dry_run {
+        write_state(&State {
+            keyword: status.keyword.to_string(),
+            set_at: Utc::now().timestamp(),
+            back_date: back_date.map(|dt| dt.timestamp()),
+            github_busy: status.github_busy,
+            asana_user_gid: ASANA_USER_GID
+                .get()
+                .cloned()
+                .or_else(|| state.as_ref().and_then(|s| s.asana_user_gid.clone())),
+            previous_slack: previous_slack.filter(|_| !is_back),
+            slack_emoji: SLACK_EMOJI.get().cloned().or_else(|| state.and_then(|s| s.slack_emoji)),
+        });
+    }
+    if !dry_run {
+        log_status_change(config, opts, &status.keyword, back_date, &results);
+    }
+    run_hook("on_set_hook", config.on_set_hook.as_deref(), &status.keyword, back_date, dry_run);
+    results.ok
+}
+
+/// With `logging`, appends a JSON line to st.log recording the change and how
+/// each service went, moving a full log to st.log.1 first. Failing to write the
+/// log only warns.
+fn log_status_change(
+    config: &Config,
+    opts: &RunOptions,
+    keyword: &str,
+    back_date: Option<DateTime<Zone>>,
+    results: &ServiceResults,
+) {
+    if !config.logging {
+        return;
+    }
+    let path = profile_file("st.log");
+    let line = serde_json::json!({
+        "time": opts.zone.now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+        "keyword": keyword,
+        "back_date": back_date.map(|dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, false)),
+        "services": results.services,
+    });
+
+    let result = (|| -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        if std::fs::metadata(&path).is_ok_and(|m| m.len() >= LOG_ROTATE_BYTES) {
+            let mut rotated = path.clone().into_os_string();
+            rotated.push(".1");
+            std::fs::rename(&path, rotated)?;
+        }
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)?;
+        std::io::Write::write_all(&mut file, format!("{line}\n").as_bytes())
+    })();
+    if let Err(e) = result {
+        eprintln!("Warning: could not write {}: {e}", path.display());
+    }
+}
+
+/// Runs a hook command through the shell once the services are updated. A
+/// failing hook only warns; it never fails the command.
+fn run_hook(name: &str, command: Option<&str>, keyword: &str, back_date: Option<DateTime<Zone>>, dry_run: bool) {
+    let Some(command) = command else {
+        return;
+    };
+    if dry_run {
+        print_dry_run_line(&format!("{name}: {command}"));
+        return;
+    }
+
+    let result = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("ST_KEYWORD", keyword)
+        .env("ST_BACK_DATE", back_date.map(|dt| dt.to_rfc3339()).unwrap_or_default())
+        .output();
+    match result {
+        Ok(out) if out.status.success() => {}
+        Ok(out) => {
+            eprintln!("Warning: {name} failed ({})", out.status);
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            if !stderr.trim().is_empty() {
+                eprintln!("{}", stderr.trim());
+            }
+        }
+        Err(e) => eprintln!("Warning: {name} could not run: {e}"),
+    }
+}
+
+/// Statuses whose Slack text ends with the back date ("Vacation. Back Friday.").
+fn shows_back_in_text(status: &Status) -> bool {
+    matches!(status.keyword.as_ref(), "vacation" | "sick" | "away" | "conference")
+}
+
+/// `restore` is the status to put back for "back" in place of "Catching up".
+fn slack_set_report(
+    status: &Status,
+    back_date: Option<DateTime<Zone>>,
+    expires: Option<DateTime<Zone>>,
+    restore: Option<&SlackProfile>,
+    config: &Config,
+    opts: &RunOptions,
+    dry_run: bool,
+) -> Vec<Report> {
+    let mut reports = Vec::new();
+    let is_back = status.keyword == "back";
+
+    // "back" clears DND then sets catching-up status
+    if is_back
+        && let Ok(token) = slack_token(config, dry_run)
+        && let Err(e) = end_slack_dnd(&token, config.retries(), dry_run)
+    {
+        reports.push(Report::new(Outcome::Error, format!("ending DND: {e}")));
+    }
+    if let Some(previous) = restore {
+        let until = opts
+            .zone
+            .timestamp_opt(previous.status_expiration, 0)
+            .single()
+            .filter(|_| previous.status_expiration > 0)
+            .map(|dt| format!(" (until {})", format_until(dt, config)))
+            .unwrap_or_default();
+        let mut report = Report::from_result(
+            restore_slack_status(previous, config, dry_run),
+            format!("Restored {} {}{until} (DND off)", previous.status_text, previous.status_emoji),
+        );
+        report.fields.insert("status_text".into(), previous.status_text.clone().into());
+        report.fields.insert("emoji".into(), previous.status_emoji.clone().into());
+        report.fields.insert("expiration".into(), previous.status_expiration.into());
+        reports.push(report);
+        return reports;
+    }
+    let show_back_in_text = shows_back_in_text(status);
+    let mut report = match set_slack_status(status, back_date, expires, show_back_in_text, config, opts, dry_run) {
+        Ok(()) => {
+            let text = match (back_date, show_back_in_text) {
+                (Some(dt), true) => status_back_text(status, dt, true, config),
+                _ => plain_status_text(status, back_date, config),
+            };
+            let shows_expiry = !show_back_in_text || expires != back_date;
+            let dnd_until = expires.or_else(|| {
+                let minutes = opts.forced_dnd_minutes?;
+                Some(opts.zone.now() + chrono::Duration::minutes(minutes))
+            });
+            let dnd_detail = match (status.slack_dnd, dnd_until) {
+                (true, Some(dt)) => format!(" (DND until {})", format_until(dt, config)),
+                (true, None) => " (DND on)".to_string(),
+                (false, Some(dt)) if shows_expiry => format!(" (until {})", format_until(dt, config)),
+                _ => String::new(),
+            };
+            let dnd_cleared = if is_back { " (DND off)" } else { "" };
+            Report::new(
+                Outcome::Ok,
+                format!("{} {}{}{}", text, status.slack_emoji, dnd_detail, dnd_cleared),
+            )
+        }
+        Err(e) => Report::error(e),
+    };
+    report.fields.insert(
+        "status_text".into(),
+        slack_status_text(status, back_date, show_back_in_text, config).into(),
+    );
+    report.fields.insert("emoji".into(), status.slack_emoji.as_ref().into());
+    report.fields.insert("expiration".into(), slack_expiration(expires).into());
+    reports.push(report);
+    reports
+}
+
+/// Posts the OOO message to `ooo_channel` for the OOO statuses. It's best
+/// effort: a failed post asks you to post it yourself rather than failing the
+/// run. Returns nothing when no channel is configured.
+fn slack_ooo_post_report(
+    status: &Status,
+    back_date: Option<DateTime<Zone>>,
+    config: &Config,
+    dry_run: bool,
+) -> Vec<Report> {
+    let Some(channel) = config.ooo_channel.as_deref() else {
+        return Vec::new();
+    };
+    if !matches!(status.keyword.as_ref(), "vacation" | "away" | "sick") {
+        return Vec::new();
+    }
+    let text = ooo_message(status, back_date, config);
+    let result = slack_token(config, dry_run)
+        .and_then(|token| post_slack_message(&token, channel, &text, dry_run));
+    let mut report = match result {
+        Ok(()) => Report::new(Outcome::Ok, format!("Posted to {channel}: {text}")),
+        Err(e) => Report::new(Outcome::Action, format!("Post to {channel} manually ({e}): {text}")),
+    };
+    report.fields.insert("ooo_message".into(), text.into());
+    vec![report]
+}
+
+/// Set busy (once per configured org), clear busy (for "back"), or no change.
+fn github_set_report(
+    status: &Status,
+    back_date: Option<DateTime<Zone>>,
+    expires: Option<DateTime<Zone>>,
+    config: &Config,
+    opts: &RunOptions,
+    dry_run: bool,
+) -> Vec<Report> {
+    if status.keyword == "back" {
+        // Leave a GitHub status st didn't set alone; with no state, clear to be safe
+        let report = match read_state() {
+            Some(state) if state.has_status() && !state.github_busy => {
+                Report::new(Outcome::Skipped, "No change (not set by st)")
+            }
+            _ => Report::from_result(clear_github_status(config, dry_run), "Cleared"),
+        };
+        return vec![report];
+    }
+    // Say so when config changed a built-in's github_busy, since it's not what the keyword usually does
+    let overridden = STATUSES
+        .iter()
+        .find(|s| s.keyword == status.keyword)
+        .is_some_and(|builtin| builtin.github_busy != status.github_busy);
+    let from_config = if overridden { " (github_busy from config)" } else { "" };
+    if !status.github_busy {
+        return vec![Report::new(Outcome::Skipped, format!("No change{from_config}"))];
+    }
+
+    let org_id = config.github_org_id.as_deref();
+    let detail = match org_id {
+        Some(id) => format!("Limited availability (org {id} only){from_config}"),
+        None => format!("Limited availability{from_config}"),
+    };
+    vec![Report::from_result(
+        set_github_status(status, back_date, expires, org_id, config, opts, dry_run),
+        detail,
+    )]
+}
+
+/// Out-of-office event for the OOO statuses. Returns nothing when no calendar
+/// is configured, so the service isn't shown at all.
+fn calendar_set_report(
+    status: &Status,
+    back_date: Option<DateTime<Zone>>,
+    leave_start: Option<NaiveDate>,
+    today: NaiveDate,
+    config: &Config,
+    opts: &RunOptions,
+    dry_run: bool,
+) -> Vec<Report> {
+    if config.gcal_calendar_id.is_none() {
+        return Vec::new();
+    }
+    let report = if !matches!(status.keyword.as_ref(), "vacation" | "away" | "sick") {
+        Report::new(Outcome::Skipped, "No change")
+    } else if let Some(back) = back_date {
+        // All-day from today, or from the first day out of a range that hasn't started
+        let start = leave_start.filter(|date| *date > today).unwrap_or(today);
+        match create_ooo_event(&plain_status_text(status, None, config), start, back, config, opts, dry_run) {
+            Ok(id) => {
+                let mut report = Report::new(Outcome::Ok, format!("Out of office until {}", format_until(back, config)));
+                report.fields.insert("event_id".into(), id.into());
+                report.fields.insert("start".into(), start.to_string().into());
+                report
+            }
+            Err(e) => Report::error(e),
+        }
+    } else {
+        Report::new(Outcome::Skipped, "No back date, no event created")
+    };
+    vec![report]
+}
+
+/// Custom status on Discord, cleared by "back". Returns nothing when no token
+/// is set, so the service isn't shown at all.
+fn discord_set_report(
+    status: &Status,
+    back_date: Option<DateTime<Zone>>,
+    expires: Option<DateTime<Zone>>,
+    config: &Config,
+    dry_run: bool,
+) -> Vec<Report> {
+    let Some(token) = discord_token(config) else {
+        return Vec::new();
+    };
+    if status.keyword == "back" {
+        return vec![Report::from_result(clear_discord_status(&token, config, dry_run), "Cleared")];
+    }
+    let show_back_in_text = shows_back_in_text(status);
+    let text = slack_status_text(status, back_date, show_back_in_text, config);
+    let until = expires.map(|dt| format!(" (until {})", format_until(dt, config))).unwrap_or_default();
+    let mut report = Report::from_result(
+        set_discord_status(&token, &text, &status.slack_emoji, expires, config, dry_run),
+        format!("{text} {}{until}", unicode_emoji(&status.slack_emoji).unwrap_or_default()),
+    );
+    report.fields.insert("status_text".into(), text.into());
+    vec![report]
+}
+
+/// Clears the Discord custom status, when a token is set.
+fn discord_clear_report(config: &Config, dry_run: bool) -> Vec<Report> {
+    match discord_token(config) {
+        Some(token) => vec![Report::from_result(clear_discord_status(&token, config, dry_run), "Cleared")],
+        None => Vec::new(),
+    }
+}
+
+/// No API for setting OOO — remind when relevant.
+fn asana_set_report(status: &Status, ooo_dates: Option<(NaiveDate, NaiveDate)>, config: &Config) -> Vec<Report> {
+    let report = if matches!(status.keyword.as_ref(), "vacation" | "away" | "sick") {
+        if asana_ooo_summary(config).is_none() {
+            let dates = match ooo_dates {
+                Some((start, end)) => format!(
+                    " ({}/{} \u{2013} {}/{})",
+                    start.month(),
+                    start.day(),
+                    end.month(),
+                    end.day()
+                ),
+                None => String::new(),
+            };
+            Report::new(
+                Outcome::Action,
+                format!("Set Out of Office manually{dates}: Profile (icon) > Set out of office"),
+            )
+        } else {
+            Report::new(Outcome::Ok, "Out of Office already set")
+        }
+    } else if status.keyword == "back" && asana_ooo_summary(config).is_some() {
+        Report::new(Outcome::Action, "Clear Out of Office manually: Profile (icon) > Set out of office")
+    } else {
+        Report::new(Outcome::Skipped, "No change")
+    };
+    vec![report]
+}
+
+fn run_clear(config: &Config, opts: &RunOptions, dry_run: bool) -> bool {
+    let asana = || {
+        let report = if asana_ooo_summary(config).is_some() {
+            Report::new(Outcome::Action, "Clear Out of Office manually: Profile (icon) > Set out of office")
+        } else {
+            Report::new(Outcome::Skipped, "No change")
+        };
+        vec![report]
+    };
+    let services: Vec<Service> = vec![
+        ("Slack", Box::new(|| vec![Report::from_result(clear_slack_status(config, dry_run), "Cleared (DND off)")])),
+        ("GitHub", Box::new(|| vec![Report::from_result(clear_github_status(config, dry_run), "Cleared")])),
+        ("Asana", Box::new(asana)),
+        ("Discord", Box::new(|| discord_clear_report(config, dry_run))),
+    ];
+    let results = run_services(config, dry_run, opts.output, services);
+    if !dry_run {
+        clear_state();
+        log_status_change(config, opts, "clear", None, &results);
+    }
+    run_hook("on_clear_hook", config.on_clear_hook.as_deref(), "clear", None, dry_run);
+    results.ok
+}
+
+/// `st snooze 45` / `st snooze 1h` snoozes for that long; `st snooze off` ends it.
+/// `None` means off.
+fn parse_snooze(input: Option<&str>) -> Result<Option<i64>> {
+    let usage = "Usage: st snooze <minutes> (e.g., 45, 1h30m) or st snooze off";
+    let input = input.with_context(|| format!("Missing snooze length\n{usage}"))?.trim();
+    if input.eq_ignore_ascii_case("off") {
+        return Ok(None);
+    }
+    let minutes = match input.parse::<i64>() {
+        Ok(minutes) => minutes,
+        Err(_) => parse_duration(input)
+            .with_context(|| format!("Could not parse snooze length: {input}\n{usage}"))?
+            .num_minutes(),
+    };
+    if minutes < 1 {
+        anyhow::bail!("Snooze length must be at least a minute: {input}");
+    }
+    // The end time is shown, so it has to be one chrono can represent
+    if chrono::Duration::try_minutes(minutes).and_then(|d| Utc::now().checked_add_signed(d)).is_none() {
+        anyhow::bail!("Snooze length is too long: {input}");
+    }
+    Ok(Some(minutes))
+}
+
+/// `st snooze`: Slack DND on (or off) without touching any status.
+fn run_snooze(minutes: Option<i64>, config: &Config, opts: &RunOptions, dry_run: bool) -> bool {
+    let slack = || {
+        let report = match slack_token(config, dry_run) {
+            Ok(token) => match minutes {
+                Some(minutes) => {
+                    let end = opts.zone.now() + chrono::Duration::minutes(minutes);
+                    Report::from_result(
+                        set_slack_dnd(&token, minutes, config.retries(), dry_run),
+                        format!("DND until {}", format_time(end, config)),
+                    )
+                }
+                None => Report::from_result(end_slack_dnd(&token, config.retries(), dry_run), "DND off"),
+            },
+            Err(e) => Report::error(e),
+        };
+        vec![report]
+    };
+    run_services(config, dry_run, opts.output, vec![("Slack", Box::new(slack))]).ok
+}
+
+/// `st back now`: clear Slack and GitHub outright instead of setting "Catching up".
+fn run_back_now(config: &Config, opts: &RunOptions, dry_run: bool) -> bool {
+    let back = find_status(config, "back").unwrap();
+    let services: Vec<Service> = vec![
+        ("Slack", Box::new(|| vec![Report::from_result(clear_slack_status(config, dry_run), "Cleared (DND off)")])),
+        ("GitHub", Box::new(|| vec![Report::from_result(clear_github_status(config, dry_run), "Cleared")])),
+        ("Asana", Box::new(|| asana_set_report(back, None, config))),
+        ("Discord", Box::new(|| discord_clear_report(config, dry_run))),
+    ];
+    let results = run_services(config, dry_run, opts.output, services);
+    if !dry_run {
+        clear_state();
+        log_status_change(config, opts, "back now", None, &results);
+    }
+    run_hook("on_clear_hook", config.on_clear_hook.as_deref(), "back", None, dry_run);
+    results.ok
+}
+
+fn run_show(config: &Config, opts: &RunOptions) {
+    match get_slack_status(config) {
+        Ok(profile) if profile.status_text.is_empty() && profile.status_emoji.is_empty() => {
+            println!("  Slack    - none");
+        }
+        Ok(profile) => {
+            let expires = match opts.zone.timestamp_opt(profile.status_expiration, 0).single() {
+                Some(dt) if profile.status_expiration > 0 => format_expiration(dt, config),
+                _ => String::new(),
+            };
+            println!("  Slack    \u{2713} {} {}{}", profile.status_text, profile.status_emoji, expires);
+        }
+        Err(e) => eprintln!("  Slack    \u{2717} {e}"),
+    }
+
+    match get_github_status(config, opts) {
+        Ok(Some(status)) => {
+            let expires = status.expires_at.map(|dt| format_expiration(dt, config)).unwrap_or_default();
+            println!("  GitHub   \u{2713} {} {}{}", status.message, status.emoji, expires);
+        }
+        Ok(None) => println!("  GitHub   - none"),
+        Err(e) => eprintln!("  GitHub   \u{2717} {e}"),
+    }
+
+    match asana_ooo_summary(config) {
+        Some(summary) => println!("  Asana    \u{2713} {summary}"),
+        None => println!("  Asana    - none"),
+    }
+
+    if let Some(state) = read_state().filter(State::has_status) {
+        let at = |timestamp: i64| opts.zone.timestamp_opt(timestamp, 0).single().map(|dt| format_until(dt, config));
+        let back = state
+            .back_date
+            .and_then(at)
+            .map(|back| format!(", back {back}"))
+            .unwrap_or_default();
+        println!(
+            "  Last set: {} at {}{back}",
+            state.keyword,
+            at(state.set_at).unwrap_or_default()
+        );
+    }
+}
+
+/// `st list`: every status keyword and what it does. Needs no tokens.
+fn run_list(config: &Config, json: bool) {
+    let keywords = available_keywords(config);
+    let statuses: Vec<&Status> = keywords.iter().filter_map(|k| find_status(config, k)).collect();
+    let yes_no = |b: bool| if b { "yes" } else { "-" };
+    let asana_reminder = |s: &Status| matches!(s.keyword.as_ref(), "vacation" | "sick" | "away" | "back");
+
+    if json {
+        let list: Vec<serde_json::Value> = statuses
+            .iter()
+            .map(|s| {
+                serde_json::json!({
+                    "keyword": s.keyword,
+                    "slack_text": s.slack_text,
+                    "slack_emoji": s.slack_emoji,
+                    "slack_dnd": s.slack_dnd,
+                    "github_busy": s.github_busy,
+                    "asana_reminder": asana_reminder(s),
+                    "description": s.description,
+                    "aliases": all_aliases(config)
+                        .iter()
+                        .filter(|(_, target)| target.eq_ignore_ascii_case(&s.keyword))
+                        .map(|(alias, _)| *alias)
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::Value::Array(list));
+        return;
+    }
+
+    let keyword_width = statuses.iter().map(|s| s.keyword.len()).max().unwrap_or(0).max(7);
+    let text_width = statuses.iter().map(|s| s.slack_text.chars().count()).max().unwrap_or(0).max(10);
+    let emoji_width = statuses.iter().map(|s| s.slack_emoji.len()).max().unwrap_or(0).max(5);
+    // The description column only shows up once a status has one
+    let described = statuses.iter().any(|s| s.description.is_some());
+    println!(
+        "  {:<keyword_width$}  {:<text_width$}  {:<emoji_width$}  DND  GitHub busy  Asana reminder{}",
+        "Keyword",
+        "Slack text",
+        "Emoji",
+        if described { "  Description" } else { "" }
+    );
+    for s in &statuses {
+        let description = match (&s.description, described) {
+            (Some(description), true) => format!("  {description}"),
+            (None, true) => "  -".to_string(),
+            _ => String::new(),
+        };
+        let line = format!(
+            "  {:<keyword_width$}  {:<text_width$}  {:<emoji_width$}  {:<3}  {:<11}  {:<14}{description}",
+            s.keyword,
+            s.slack_text,
+            s.slack_emoji,
+            yes_no(s.slack_dnd),
+            yes_no(s.github_busy),
+            yes_no(asana_reminder(s))
+        );
+        println!("{}", line.trim_end());
+    }
+    if !all_aliases(config).is_empty() {
+        println!("\n  Aliases: {}", alias_list(config));
+    }
 }
 
-// --- GitHub integration ---
+/// `st config check`: validates config.toml and prints what it resolves to.
+/// Returns false if anything is wrong.
+fn run_config_check() -> bool {
+    let path = config_path();
+    let config = match read_config() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("  \u{2717} {e:#}");
+            return false;
+        }
+    };
+    if path.exists() {
+        println!("Config: {}", path.display());
+    } else {
+        println!("Config: {} (not found, using defaults)", path.display());
+    }
 
-fn github_graphql(token: &str, body: &serde_json::Value) -> Result<serde_json::Value> {
-    let resp: serde_json::Value = ureq::post("https://api.github.com/graphql")
-        .header("Authorization", &format!("Bearer {token}"))
-        .header("User-Agent", "st-cli")
-        .send_json(body)?
-        .into_body()
-        .read_json()?;
+    let tokens = [
+        ("slack_token", "SLACK_PAT", config.slack_token.as_deref()),
+        ("github_token", "GITHUB_PAT", config.github_token.as_deref()),
+        ("asana_token", "ASANA_PAT", config.asana_token.as_deref()),
+        ("gcal_token", "GCAL_TOKEN", config.gcal_token.as_deref()),
+        ("discord_token", "DISCORD_TOKEN", config.discord_token.as_deref()),
+    ];
+    for (key, var, value) in tokens {
+        let var = token_var(var);
+        let resolved = match (std::env::var(&var), value) {
+            (Ok(token), _) => format!("{} (from {var})", redact(&token)),
+            (Err(_), Some(token)) => redact(token),
+            (Err(_), None) => "not set".to_string(),
+        };
+        println!("  {key:<22} {resolved}");
+    }
 
-    if let Some(errors) = resp.get("errors") {
-        anyhow::bail!("GraphQL error: {errors}");
+    let show = |value: Option<&str>| value.unwrap_or("not set").to_string();
+    let hour = |value: Option<u32>, default: u32| match value {
+        Some(h) => h.to_string(),
+        None => format!("{default} (default)"),
+    };
+    let values = [
+        ("github_org_id", show(config.github_org_id.as_deref())),
+        ("asana_user_gid", show(config.asana_user_gid.as_deref())),
+        ("asana_email", show(config.asana_email.as_deref())),
+        ("asana_workspace_gid", show(config.asana_workspace_gid.as_deref())),
+        ("gcal_calendar_id", show(config.gcal_calendar_id.as_deref())),
+        ("ooo_channel", show(config.ooo_channel.as_deref())),
+        ("ooo_message_template", show(config.ooo_message_template.as_deref())),
+        ("default_back_hour", hour(config.default_back_hour, DEFAULT_BACK_HOUR)),
+        ("eob_hour", hour(config.eob_hour, DEFAULT_EOB_HOUR)),
+        ("morning_hour", hour(config.morning_hour.or(config.default_back_hour), DEFAULT_BACK_HOUR)),
+        ("afternoon_hour", hour(config.afternoon_hour, DEFAULT_AFTERNOON_HOUR)),
+        ("evening_hour", hour(config.evening_hour, DEFAULT_EVENING_HOUR)),
+        ("tonight_hour", hour(config.tonight_hour, DEFAULT_TONIGHT_HOUR)),
+        ("retries", hour(config.retries, DEFAULT_RETRIES)),
+        ("confirm_threshold_days", hour(config.confirm_threshold_days, DEFAULT_CONFIRM_THRESHOLD_DAYS)),
+        ("lunch_round_to_minutes", hour(config.lunch_round_to_minutes, DEFAULT_LUNCH_ROUND_MINUTES)),
+        ("lunch_duration_minutes", hour(config.lunch_duration_minutes, DEFAULT_LUNCH_MINUTES)),
+        ("focus_minutes", hour(config.focus_minutes, DEFAULT_FOCUS_MINUTES)),
+        ("commute_minutes", hour(config.commute_minutes, DEFAULT_COMMUTE_MINUTES)),
+        ("timezone", config.timezone.clone().unwrap_or_else(|| "system".to_string())),
+        ("back_format", show(config.back_format.as_deref())),
+        ("time_format", if config.time_format == TimeFormat::H24 { "24h" } else { "12h" }.to_string()),
+        ("date_order", if config.date_order == DateOrder::Dmy { "dmy" } else { "mdy" }.to_string()),
+        ("skip_weekends", config.skip_weekends.to_string()),
+        ("github_expire_at_end_of_day", config.github_expire_at_end_of_day.to_string()),
+        ("natural_dates", config.natural_dates.to_string()),
+        ("infer_pm", config.infer_pm.unwrap_or(true).to_string()),
+        ("ignore_missing_tokens", config.ignore_missing_tokens.to_string()),
+        ("on_set_hook", show(config.on_set_hook.as_deref())),
+        ("on_clear_hook", show(config.on_clear_hook.as_deref())),
+        ("logging", config.logging.to_string()),
+        ("holidays", config.holidays.join(", ")),
+    ];
+    for (key, value) in values {
+        println!("  {key:<22} {value}");
+    }
+    for (name, value) in &config.times {
+        println!("  [times] {name} = {value}");
+    }
+    for (alias, target) in &config.aliases {
+        println!("  [aliases] {alias} = {target}");
+    }
+    for status in &config.statuses {
+        println!("  [[status]] {} = {} {}", status.keyword, status.slack_text, status.slack_emoji);
     }
 
-    Ok(resp)
+    let problems = config_problems(&config);
+    for problem in &problems {
+        eprintln!("  \u{2717} {problem}");
+    }
+    problems.is_empty()
 }
 
-fn set_github_status(
-    status: &Status,
-    back_date: Option<DateTime<Local>>,
-    org_id: Option<&str>,
-) -> Result<()> {
-    let token = std::env::var("GITHUB_PAT").context("GITHUB_PAT not set")?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if !status.github_busy {
-        return Ok(());
+    fn config() -> Config {
+        Config::default()
     }
 
-    let mut input = format!(
-        "message: \"{}\", emoji: \"{}\", limitedAvailability: true",
-        status.slack_text, status.slack_emoji,
-    );
+    /// Options in a fixed zone, so tests don't depend on the machine's.
+    fn opts() -> RunOptions {
+        RunOptions {
+            zone: Zone::Named(chrono_tz::America::Chicago),
+            ..RunOptions::default()
+        }
+    }
 
-    if let Some(dt) = back_date {
-        input.push_str(&format!(", expiresAt: \"{}\"", dt.to_utc().format("%Y-%m-%dT%H:%M:%SZ")));
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
     }
 
-    if let Some(id) = org_id {
-        input.push_str(&format!(", organizationId: \"{}\"", id));
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
     }
 
-    let query = format!(
-        "mutation {{ changeUserStatus(input: {{ {input} }}) {{ status {{ message }} }} }}"
-    );
+    /// A pinned "now" in the config's zone.
+    fn at(config: &Config, day: NaiveDate, hour: u32, minute: u32) -> DateTime<Zone> {
+        to_local_datetime(day, time(hour, minute), config, &opts())
+    }
 
-    let body = serde_json::json!({ "query": query });
-    github_graphql(&token, &body)?;
+    // 2026-03-09 is a Monday; the tests below run through that week.
 
-    Ok(())
-}
+    #[test]
+    fn iso_dates() {
+        let today = date(2026, 3, 1);
+        let config = config();
+        assert_eq!(parse_date("2026-03-10", today, &config).unwrap(), date(2026, 3, 10));
+        assert_eq!(parse_date("2026/3/1", today, &config).unwrap(), date(2026, 3, 1));
+        assert_eq!(parse_date("3-10-2026", today, &config).unwrap(), date(2026, 3, 10));
+    }
 
-fn clear_github_status() -> Result<()> {
-    let token = std::env::var("GITHUB_PAT").context("GITHUB_PAT not set")?;
+    #[test]
+    fn this_and_next_weekday_for_every_day_of_the_week() {
+        let config = config();
+        // Mon 3/9 through Sun 3/15
+        let this_friday = [13, 13, 13, 13, 13, 20, 20];
+        let next_friday = [20, 20, 20, 20, 27, 27, 27];
+        for offset in 0..7 {
+            let today = date(2026, 3, 9 + offset);
+            let this = parse_date("this friday", today, &config).unwrap();
+            let next = parse_date("next friday", today, &config).unwrap();
+            assert_eq!(this, date(2026, 3, this_friday[offset as usize]), "this friday from {today}");
+            assert_eq!(next, date(2026, 3, next_friday[offset as usize]), "next friday from {today}");
+        }
+    }
 
-    let body: serde_json::Value = serde_json::from_str(
-        r#"{"query":"mutation { changeUserStatus(input: {}) { clientMutationId } }"}"#,
-    )?;
+    #[test]
+    fn joined_next_weekday() {
+        let today = date(2026, 3, 11);
+        assert_eq!(parse_date("next-friday", today, &config()).unwrap(), date(2026, 3, 20));
+        assert_eq!(parse_date("this-friday", today, &config()).unwrap(), date(2026, 3, 13));
+    }
 
-    github_graphql(&token, &body)?;
+    #[test]
+    fn next_weekday_back_date_uses_the_pinned_clock() {
+        let config = config();
+        let opts = opts();
+        let now = at(&config, date(2026, 3, 12), 10, 0);
+        let back = parse_back_date("next friday", None, None, now, &config, &opts).unwrap().0;
+        assert_eq!(back, at(&config, date(2026, 3, 20), DEFAULT_BACK_HOUR, 0));
+    }
 
-    Ok(())
-}
+    #[test]
+    fn next_weekday_is_a_week_past_the_coming_one() {
+        let config = config();
+        // Wednesday 3/11: "next friday" is 9 days out, not 2
+        let today = date(2026, 3, 11);
+        let expected = [
+            ("monday", 23),
+            ("tuesday", 24),
+            ("wednesday", 25),
+            ("thursday", 19),
+            ("friday", 20),
+            ("saturday", 21),
+            ("sunday", 22),
+        ];
+        for (day, expected) in expected {
+            let input = format!("next {day}");
+            assert_eq!(parse_date(&input, today, &config).unwrap(), date(2026, 3, expected), "{input}");
+        }
+    }
 
-// --- Slack integration ---
+    #[test]
+    fn month_name_dates() {
+        let config = config();
+        let today = date(2026, 3, 1);
+        for input in ["march 10", "Mar 10", "10 march", "mar10", "march-10"] {
+            assert_eq!(parse_date(input, today, &config).unwrap(), date(2026, 3, 10), "{input}");
+        }
+    }
 
-fn set_slack_status(
-    status: &Status,
-    back_date: Option<DateTime<Local>>,
-    show_back_in_text: bool,
-) -> Result<()> {
-    let token = std::env::var("SLACK_PAT").context("SLACK_PAT not set")?;
+    #[test]
+    fn month_name_dates_roll_over_the_year_end() {
+        let config = config();
+        let today = date(2026, 12, 20);
+        assert_eq!(parse_date("dec 25", today, &config).unwrap(), date(2026, 12, 25));
+        assert_eq!(parse_date("jan 5", today, &config).unwrap(), date(2027, 1, 5));
+        assert_eq!(parse_date("dec 1", today, &config).unwrap(), date(2027, 12, 1));
+    }
 
-    let text = match (back_date, show_back_in_text) {
-        (Some(dt), true) => format!("{}. {}", status.slack_text, format_back_date(dt)),
-        _ => status.slack_text.to_string(),
-    };
+    #[test]
+    fn month_name_dates_reject_invalid_days() {
+        let today = date(2026, 1, 10);
+        assert!(parse_date("feb 30", today, &config()).is_err());
+        assert!(parse_date("april 31", today, &config()).is_err());
+    }
 
-    let expiration = match back_date {
-        Some(dt) => dt.timestamp(),
-        None => 0,
-    };
+    #[test]
+    fn in_days_and_weeks() {
+        let config = config();
+        let today = date(2026, 3, 9);
+        assert_eq!(parse_date("in 1 day", today, &config).unwrap(), date(2026, 3, 10));
+        assert_eq!(parse_date("in 3 days", today, &config).unwrap(), date(2026, 3, 12));
+        assert_eq!(parse_date("in 1 week", today, &config).unwrap(), date(2026, 3, 16));
+        assert_eq!(parse_date("in 2 weeks", today, &config).unwrap(), date(2026, 3, 23));
+    }
 
-    let profile = serde_json::json!({
-        "profile": {
-            "status_text": text,
-            "status_emoji": status.slack_emoji,
-            "status_expiration": expiration
+    #[test]
+    fn in_days_rejects_bad_counts() {
+        let config = config();
+        let today = date(2026, 3, 9);
+        for input in ["in 0 days", "in -2 days", "in three days", "in 3 fortnights", "in 99999999999 days"] {
+            assert!(parse_date(input, today, &config).is_err(), "{input}");
         }
-    });
+    }
 
-    let resp: SlackResponse = ureq::post("https://slack.com/api/users.profile.set")
-        .header("Authorization", &format!("Bearer {token}"))
-        .send_json(&profile)?
-        .into_body()
-        .read_json()?;
+    #[test]
+    fn date_order() {
+        let today = date(2026, 1, 1);
+        let mdy = config();
+        let dmy = Config { date_order: DateOrder::Dmy, ..config() };
+        assert_eq!(parse_date("3/10", today, &mdy).unwrap(), date(2026, 3, 10));
+        assert_eq!(parse_date("3/10", today, &dmy).unwrap(), date(2026, 10, 3));
+        assert_eq!(parse_date("3-10-2026", today, &dmy).unwrap(), date(2026, 10, 3));
+        // Only one reading is a real date, whichever the order
+        assert_eq!(parse_date("13/10", today, &mdy).unwrap(), date(2026, 10, 13));
+        assert_eq!(parse_date("10/13", today, &dmy).unwrap(), date(2026, 10, 13));
+    }
 
-    if !resp.ok {
-        anyhow::bail!("Slack users.profile.set: {}", resp.error.unwrap_or_default());
+    #[test]
+    fn date_order_error_examples() {
+        let today = date(2026, 1, 1);
+        let dmy = Config { date_order: DateOrder::Dmy, ..config() };
+        let e = parse_date("soon", today, &config()).unwrap_err().to_string();
+        assert!(e.contains("3/10, 3-10-2026"), "{e}");
+        let e = parse_date("soon", today, &dmy).unwrap_err().to_string();
+        assert!(e.contains("10/3, 10-3-2026"), "{e}");
     }
 
-    if status.slack_dnd {
-        let minutes = match back_date {
-            Some(dt) => {
-                let diff = dt.signed_duration_since(Local::now()).num_minutes();
-                if diff > 0 { diff } else { 1440 }
-            }
-            None => 1440,
-        };
-        set_slack_dnd(&token, minutes)?;
+    #[test]
+    fn parse_time_rejects_out_of_range() {
+        let config = config();
+        let cases = [
+            ("25:00", "hours must be 0\u{2013}23"),
+            ("24:00", "hours must be 0\u{2013}23"),
+            ("9:75", "minutes must be 0\u{2013}59"),
+            ("13pm", "hours must be 1\u{2013}12 with am/pm"),
+            ("0am", "hours must be 1\u{2013}12 with am/pm"),
+            ("9:5", "minutes must be two digits"),
+            ("soon", "Could not parse time"),
+        ];
+        for (input, reason) in cases {
+            let e = parse_time(Some(input), &config).unwrap_err().to_string();
+            assert!(e.contains(reason), "{input}: {e}");
+        }
     }
 
-    Ok(())
-}
+    #[test]
+    fn spring_forward_gap_moves_past_it() {
+        let config = config();
+        let opts = opts();
+        // Chicago skips 2:00-3:00am on 3/8/2026
+        let dt = to_local_datetime(date(2026, 3, 8), time(2, 30), &config, &opts);
+        assert_eq!(dt.time(), time(3, 30));
+        assert_eq!(dt.offset().fix().local_minus_utc(), -5 * 3600);
+    }
 
-fn set_slack_dnd(token: &str, minutes: i64) -> Result<()> {
-    let resp: SlackResponse = ureq::post("https://slack.com/api/dnd.setSnooze")
-        .header("Authorization", &format!("Bearer {token}"))
-        .header("Content-Type", "application/x-www-form-urlencoded")
-        .send_form([("num_minutes", &minutes.to_string())])?
-        .into_body()
-        .read_json()?;
+    #[test]
+    fn fall_back_ambiguity_takes_the_earlier_time() {
+        let config = config();
+        let opts = opts();
+        // 1:00-2:00am happens twice on 11/1/2026; the first is still CDT
+        let dt = to_local_datetime(date(2026, 11, 1), time(1, 30), &config, &opts);
+        assert_eq!(dt.time(), time(1, 30));
+        assert_eq!(dt.offset().fix().local_minus_utc(), -5 * 3600);
+    }
 
-    if !resp.ok {
-        anyhow::bail!("Slack dnd.setSnooze: {}", resp.error.unwrap_or_default());
+    #[test]
+    fn default_back_hour_from_config() {
+        let opts = opts();
+        let config = Config { default_back_hour: Some(9), ..config() };
+        let now = at(&config, date(2026, 3, 9), 10, 0);
+        let back = parse_back_date("friday", None, None, now, &config, &opts).unwrap().0;
+        assert_eq!(back, at(&config, date(2026, 3, 13), 9, 0));
     }
 
-    Ok(())
-}
+    #[test]
+    fn skip_weekends_moves_computed_dates_to_monday() {
+        let opts = opts();
+        let config = Config { skip_weekends: true, ..config() };
+        let friday = at(&config, date(2026, 3, 13), 10, 0);
+        let back = parse_back_date("tomorrow", None, None, friday, &config, &opts).unwrap().0;
+        assert_eq!(back.date_naive(), date(2026, 3, 16));
 
-fn end_slack_dnd(token: &str) -> Result<()> {
-    let resp: SlackResponse = ureq::post("https://slack.com/api/dnd.endSnooze")
-        .header("Authorization", &format!("Bearer {token}"))
-        .header("Content-Type", "application/x-www-form-urlencoded")
-        .send_form(std::iter::empty::<(&str, &str)>())?
-        .into_body()
-        .read_json()?;
+        let thursday = at(&config, date(2026, 3, 12), 10, 0);
+        let back = parse_back_date("+2", None, None, thursday, &config, &opts).unwrap().0;
+        assert_eq!(back.date_naive(), date(2026, 3, 16));
+    }
 
-    // dnd.endSnooze returns ok=false with "snooze_not_active" if DND isn't on, which is fine
-    if !resp.ok && resp.error.as_deref() != Some("snooze_not_active") {
-        anyhow::bail!("Slack dnd.endSnooze: {}", resp.error.unwrap_or_default());
+    #[test]
+    fn skip_weekends_honors_named_days() {
+        let opts = opts();
+        let config = Config { skip_weekends: true, ..config() };
+        let thursday = at(&config, date(2026, 3, 12), 10, 0);
+        let back = parse_back_date("saturday", None, None, thursday, &config, &opts).unwrap().0;
+        assert_eq!(back.date_naive(), date(2026, 3, 14));
     }
 
-    Ok(())
-}
+    #[test]
+    fn next_week_end_of_week_end_of_month() {
+        let config = config();
+        let cases = [
+            // (today, input, expected)
+            (date(2026, 3, 11), "next week", date(2026, 3, 16)),
+            (date(2026, 3, 9), "next week", date(2026, 3, 16)),
+            (date(2026, 3, 15), "next week", date(2026, 3, 16)),
+            (date(2026, 2, 27), "next week", date(2026, 3, 2)),
+            (date(2026, 3, 11), "end of week", date(2026, 3, 13)),
+            (date(2026, 3, 13), "end of week", date(2026, 3, 13)),
+            (date(2026, 3, 14), "end of week", date(2026, 3, 20)),
+            (date(2026, 2, 10), "end of month", date(2026, 2, 28)),
+            (date(2028, 2, 10), "end of month", date(2028, 2, 29)),
+            (date(2026, 2, 28), "end of month", date(2026, 3, 31)),
+            (date(2026, 1, 31), "end of month", date(2026, 2, 28)),
+            (date(2026, 12, 31), "end of month", date(2027, 1, 31)),
+        ];
+        for (today, input, expected) in cases {
+            assert_eq!(parse_date(input, today, &config).unwrap(), expected, "{input} from {today}");
+        }
+    }
 
-fn clear_slack_status() -> Result<()> {
-    let token = std::env::var("SLACK_PAT").context("SLACK_PAT not set")?;
+    #[test]
+    fn coarse_dates_format_like_any_other() {
+        let config = config();
+        let opts = opts();
+        let now = at(&config, date(2026, 3, 11), 10, 0);
+        let back = parse_back_date("end of week", None, None, now, &config, &opts).unwrap().0;
+        assert_eq!(format_back_date(back, now.date_naive(), &config), "Back Friday.");
+        let back = parse_back_date("end of month", None, None, now, &config, &opts).unwrap().0;
+        assert_eq!(format_back_date(back, now.date_naive(), &config), "Back 3/31.");
+    }
 
-    let profile = serde_json::json!({
-        "profile": {
-            "status_text": "",
-            "status_emoji": "",
-            "status_expiration": 0
+    #[test]
+    fn zero_padded_24_hour_times() {
+        let config = config();
+        let cases = [("00:00", time(0, 0)), ("23:59", time(23, 59)), ("0:05", time(0, 5)), ("08:30", time(8, 30))];
+        for (input, expected) in cases {
+            assert_eq!(parse_time(Some(input), &config).unwrap(), expected, "{input}");
         }
-    });
+        assert!(parse_time(Some("24:00"), &config).is_err());
+        assert!(parse_time(Some("12:60"), &config).is_err());
+    }
 
-    let resp: SlackResponse = ureq::post("https://slack.com/api/users.profile.set")
-        .header("Authorization", &format!("Bearer {token}"))
-        .send_json(&profile)?
-        .into_body()
-        .read_json()?;
+    #[test]
+    fn durations_count_from_now() {
+        let config = config();
+        let now = at(&config, date(2026, 3, 9), 10, 0);
+        for (input, minutes) in [("45m", 45), ("2h", 120), ("90m", 90), ("1h30m", 90)] {
+            let back = parse_back_duration(input, now).unwrap();
+            assert_eq!(back - now, chrono::Duration::minutes(minutes), "{input}");
+        }
+    }
 
-    if !resp.ok {
-        anyhow::bail!("Slack users.profile.set: {}", resp.error.unwrap_or_default());
+    #[test]
+    fn durations_crossing_midnight_show_the_next_day() {
+        let config = config();
+        let now = at(&config, date(2026, 3, 9), 23, 30);
+        let back = parse_back_duration("1h30m", now).unwrap();
+        assert_eq!(format_back_date_with_time(back, now.date_naive(), &config), "Back Tuesday 1am.");
     }
 
-    end_slack_dnd(&token)?;
+    #[test]
+    fn zero_and_negative_durations_are_rejected() {
+        let now = at(&config(), date(2026, 3, 9), 10, 0);
+        for input in ["0m", "0h", "0h0m", "-1h"] {
+            assert!(parse_back_duration(input, now).is_err(), "{input}");
+        }
+    }
 
-    Ok(())
-}
+    #[test]
+    fn meeting_durations_too_long_to_represent_are_rejected() {
+        let now = at(&config(), date(2026, 3, 9), 10, 0);
+        assert!(parse_back_duration("4000000000h", now).is_err());
+    }
 
-#[derive(Deserialize)]
-struct SlackResponse {
-    ok: bool,
-    error: Option<String>,
-}
+    #[test]
+    fn back_text_is_in_the_configured_zone() {
+        // 14:00 UTC on Friday 3/13 is 9am in Chicago and 11pm in Tokyo
+        let instant = Utc.with_ymd_and_hms(2026, 3, 13, 14, 0, 0).unwrap();
+        let today = date(2026, 3, 9);
 
-// --- Asana (no API for setting OOO — can only read vacation_dates) ---
+        let back = instant.with_timezone(&opts().zone);
+        assert_eq!(format_back_date_with_time(back, today, &config()), "Back Friday 9am.");
 
-#[derive(Deserialize)]
-struct AsanaResponse {
-    data: Vec<AsanaWorkspaceMembership>,
-}
+        let back = instant.with_timezone(&Zone::Named(chrono_tz::Asia::Tokyo));
+        assert_eq!(format_back_date_with_time(back, today, &config()), "Back Friday 11pm.");
+    }
 
-#[derive(Deserialize)]
-struct AsanaWorkspaceMembership {
-    vacation_dates: Option<AsanaVacationDates>,
-}
+    #[test]
+    fn configured_zone_times_have_the_right_timestamp() {
+        let tokyo = RunOptions { zone: Zone::Named(chrono_tz::Asia::Tokyo), ..opts() };
+        let back = to_local_datetime(date(2026, 3, 13), time(9, 0), &config(), &tokyo);
+        assert_eq!(slack_expiration(Some(back)), Utc.with_ymd_and_hms(2026, 3, 13, 0, 0, 0).unwrap().timestamp());
+    }
 
-#[derive(Deserialize)]
-#[allow(dead_code)]
-struct AsanaVacationDates {
-    start_on: Option<String>,
-    end_on: Option<String>,
-}
+    #[test]
+    fn ordinal_days() {
+        let config = config();
+        let cases = [
+            (date(2026, 3, 20), "25th", date(2026, 3, 25)),
+            (date(2026, 3, 20), "15th", date(2026, 4, 15)),
+            (date(2026, 3, 20), "the 1st", date(2026, 4, 1)),
+            // A day the month doesn't have rolls on to the next month that does
+            (date(2026, 4, 10), "31st", date(2026, 5, 31)),
+            (date(2026, 1, 31), "29th", date(2026, 3, 29)),
+            (date(2026, 2, 1), "30th", date(2026, 3, 30)),
+            (date(2028, 2, 1), "29th", date(2028, 2, 29)),
+        ];
+        for (today, input, expected) in cases {
+            assert_eq!(parse_date(input, today, &config).unwrap(), expected, "{input} from {today}");
+        }
+        assert!(parse_date("15st", date(2026, 3, 1), &config).is_err());
+        assert!(parse_date("32nd", date(2026, 3, 1), &config).is_err());
+    }
 
-fn asana_ooo_is_set(config: &Config) -> Result<bool> {
-    let token = std::env::var("ASANA_PAT").context("ASANA_PAT not set")?;
-    let user_gid = config
-        .asana_user_gid
-        .as_deref()
-        .context("asana_user_gid not set in config")?;
+    #[test]
+    fn date_typos_suggest_the_closest_word() {
+        assert_eq!(suggest_date_word("firday").as_deref(), Some("friday"));
+        assert_eq!(suggest_date_word("next firday").as_deref(), Some("next friday"));
+        assert_eq!(suggest_date_word("tomorow").as_deref(), Some("tomorrow"));
+        // Exact words and garbage get no suggestion
+        assert_eq!(suggest_date_word("friday"), None);
+        assert_eq!(suggest_date_word("xyzzy"), None);
 
-    let url = format!(
-        "https://app.asana.com/api/1.0/users/{user_gid}/workspace_memberships?opt_fields=vacation_dates"
-    );
+        let err = parse_date("firday", date(2026, 3, 9), &config()).unwrap_err().to_string();
+        assert!(err.contains("did you mean 'friday'?"), "{err}");
+    }
 
-    let resp: AsanaResponse = ureq::get(&url)
-        .header("Authorization", &format!("Bearer {token}"))
-        .call()?
-        .into_body()
-        .read_json()?;
+    #[test]
+    fn numeric_date_errors_name_the_bad_part() {
+        assert_eq!(numeric_date_problem("3//10", DateOrder::Mdy).as_deref(), Some("missing day"));
+        assert_eq!(numeric_date_problem("3-", DateOrder::Mdy).as_deref(), Some("missing day"));
+        assert_eq!(numeric_date_problem("13/40", DateOrder::Mdy).as_deref(), Some("month must be 1-12, got 13"));
+        assert_eq!(numeric_date_problem("10/40", DateOrder::Dmy).as_deref(), Some("month must be 1-12, got 40"));
+        assert_eq!(numeric_date_problem("3/10", DateOrder::Mdy), None);
 
-    Ok(resp.data.iter().any(|m| m.vacation_dates.is_some()))
-}
+        let err = parse_date("3//10", date(2026, 3, 9), &config()).unwrap_err().to_string();
+        assert!(err.starts_with("Could not parse date: 3//10 (missing day)"), "{err}");
+    }
 
-fn asana_ooo_summary(config: &Config) -> Option<String> {
-    match asana_ooo_is_set(config) {
-        Ok(true) => Some("OOO is set".into()),
-        Ok(false) => None,
-        Err(_) => None,
+    #[test]
+    fn year_first_dates_parse_as_iso() {
+        let (config, today) = (config(), date(2026, 3, 9));
+        assert_eq!(parse_date("2026-03-10", today, &config).unwrap(), date(2026, 3, 10));
+        assert_eq!(parse_date("2026-3-5", today, &config).unwrap(), date(2026, 3, 5));
+        assert_eq!(parse_date("2027/3/5", today, &config).unwrap(), date(2027, 3, 5));
+        assert_eq!(parse_date("3-10-2026", today, &config).unwrap(), date(2026, 3, 10));
     }
-}
 
-// --- CLI ---
+    #[test]
+    fn past_back_dates_are_rejected_unless_allowed() {
+        let now = at(&config(), date(2026, 3, 9), 10, 0);
+        let err = parse_back_date("1-5-2024", None, None, now, &config(), &opts()).unwrap_err().to_string();
+        assert!(err.starts_with("Back date 1-5-2024 is in the past (Fri Jan 5, 2024 7am)"), "{err}");
+        assert!(err.contains("--allow-past"), "{err}");
 
-#[derive(Parser)]
-#[command(name = "st", about = "Set your status across services", version)]
-struct Cli {
-    /// Status keyword: lunch, zoom, tuple, meet, eod, vacation, sick, away, back, clear
-    keyword: String,
+        let opts = RunOptions { allow_past: true, ..opts() };
+        let back = parse_back_date("1-5-2024", None, None, now, &config(), &opts).unwrap().0;
+        assert_eq!(back.date_naive(), date(2024, 1, 5));
+    }
 
-    /// Back date: when you'll return (e.g., friday, 2/28, 2026-03-10, tomorrow)
-    back_date: Option<String>,
+    #[test]
+    fn past_back_dates_never_set_dnd() {
+        let config = config();
+        let (strict, allowed) = (opts(), RunOptions { allow_past: true, ..opts() });
+        let lunch = find_status(&config, "lunch").unwrap();
+        let past = to_local_datetime(date(2024, 1, 5), time(13, 0), &config, &strict);
+        let err = set_slack_status(lunch, Some(past), Some(past), false, &config, &strict, true)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("not setting DND"), "{err}");
+        assert!(set_slack_status(lunch, Some(past), Some(past), false, &config, &allowed, true).is_ok());
+    }
 
-    /// Back time: what time you'll return (e.g., 8am, 9:30am, 15:00). Defaults to 7am.
-    back_time: Option<String>,
-}
+    #[test]
+    fn dashed_dates_fall_back_to_the_swapped_reading() {
+        let today = date(2026, 3, 9);
+        let mdy = config();
+        assert_eq!(parse_date("10-28-27", today, &mdy).unwrap(), date(2027, 10, 28));
+        assert_eq!(parse_date("28-10-27", today, &mdy).unwrap(), date(2027, 10, 28));
+        // Ambiguous dates keep the configured order
+        assert_eq!(parse_date("3-4-27", today, &mdy).unwrap(), date(2027, 3, 4));
 
-fn main() {
-    let cli = Cli::parse();
-    let config = load_config();
-    let keyword = cli.keyword.to_lowercase();
-    let is_clear = keyword == "clear";
+        let dmy = Config { date_order: DateOrder::Dmy, ..config() };
+        assert_eq!(parse_date("3-4-27", today, &dmy).unwrap(), date(2027, 4, 3));
+        assert_eq!(parse_date("10-28-27", today, &dmy).unwrap(), date(2027, 10, 28));
+    }
 
-    if !is_clear && find_status(&keyword).is_none() {
-        eprintln!(
-            "Unknown keyword: {keyword}\nAvailable: lunch, zoom, tuple, meet, eod, vacation, sick, away, back, clear"
+    #[test]
+    fn default_lunch_rounds_up_before_adding_the_hour() {
+        let config = config();
+        let day = date(2026, 3, 9);
+        assert_eq!(default_lunch_back(at(&config, day, 12, 7), 15, 60), at(&config, day, 13, 15));
+        // Always moves forward, even from a round minute
+        assert_eq!(default_lunch_back(at(&config, day, 12, 0), 15, 60), at(&config, day, 13, 15));
+        // Minute 59 rolls into the next hour
+        assert_eq!(default_lunch_back(at(&config, day, 12, 59), 15, 60), at(&config, day, 14, 0));
+        assert_eq!(default_lunch_back(at(&config, day, 12, 3), 5, 60), at(&config, day, 13, 5));
+        assert_eq!(default_lunch_back(at(&config, day, 12, 3), 5, 30), at(&config, day, 12, 35));
+    }
+
+    #[test]
+    fn bare_early_hours_read_as_pm_once_am_has_passed() {
+        let config = config();
+        let opts = opts();
+        let monday = date(2026, 3, 9);
+        let now = at(&config, monday, 10, 0);
+        assert_eq!(
+            parse_back_date("today", Some("3"), None, now, &config, &opts).unwrap().0,
+            at(&config, monday, 15, 0)
         );
-        std::process::exit(1);
+        assert_eq!(
+            parse_back_date("today", Some("3:30"), None, now, &config, &opts).unwrap().0,
+            at(&config, monday, 15, 30)
+        );
+        // A future day keeps the hour as given
+        let friday = date(2026, 3, 13);
+        assert_eq!(
+            parse_back_date("friday", Some("3"), None, now, &config, &opts).unwrap().0,
+            at(&config, friday, 3, 0)
+        );
+        // Same-day statuses always read it as pm
+        let early = at(&config, monday, 1, 0);
+        assert_eq!(parse_lunch_back_time(Some("3"), early, &config, &opts).unwrap(), at(&config, monday, 15, 0));
+        // Only 1 through 6, and only without am/pm
+        assert_eq!(parse_lunch_back_time(Some("11"), early, &config, &opts).unwrap(), at(&config, monday, 11, 0));
+        assert_eq!(parse_lunch_back_time(Some("3am"), early, &config, &opts).unwrap(), at(&config, monday, 3, 0));
     }
 
-    let back_dt = if keyword == "lunch" {
-        let time = cli.back_date.as_deref(); // for lunch, second arg is a time
-        Some(parse_lunch_back_time(time).unwrap_or_else(|e| {
-            eprintln!("{e}");
-            std::process::exit(1);
-        }))
-    } else {
-        cli.back_date.map(|s| {
-            parse_back_date(&s, cli.back_time.as_deref()).unwrap_or_else(|e| {
-                eprintln!("{e}");
-                std::process::exit(1);
-            })
-        })
-    };
+    #[test]
+    fn infer_pm_can_be_turned_off() {
+        let opts = opts();
+        let config = Config { infer_pm: Some(false), ..config() };
+        let monday = date(2026, 3, 9);
+        let now = at(&config, monday, 1, 0);
+        assert_eq!(parse_lunch_back_time(Some("3"), now, &config, &opts).unwrap(), at(&config, monday, 3, 0));
+        let later = at(&config, monday, 10, 0);
+        assert!(parse_back_date("today", Some("3"), None, later, &config, &opts).is_err());
+    }
 
-    if is_clear {
-        run_clear(&config);
-    } else {
-        let status = find_status(&keyword).unwrap();
-        run_set(status, back_dt, &config);
+    #[test]
+    fn noon_and_midnight_are_times() {
+        let config = config();
+        let opts = opts();
+        for input in ["noon", "12n", "12pm", "12:00"] {
+            assert_eq!(parse_time(Some(input), &config).unwrap(), time(12, 0), "{input}");
+        }
+        for input in ["midnight", "12m", "12am", "0:00"] {
+            assert_eq!(parse_time(Some(input), &config).unwrap(), NaiveTime::MIN, "{input}");
+        }
+        // "12m" is midnight, not twelve minutes
+        assert_eq!(parse_duration("12m"), None);
+        assert!(parse_duration("13m").is_some());
+
+        let monday = date(2026, 3, 9);
+        let now = at(&config, monday, 11, 0);
+        let tonight = at(&config, date(2026, 3, 10), 0, 0);
+        assert_eq!(parse_lunch_back_time(Some("12m"), now, &config, &opts).unwrap(), tonight);
+        assert_eq!(parse_lunch_back_time(Some("midnight"), now, &config, &opts).unwrap(), tonight);
+        assert_eq!(parse_lunch_back_time(Some("noon"), now, &config, &opts).unwrap(), at(&config, monday, 12, 0));
+        assert_eq!(parse_until("midnight", now, &config, &opts).unwrap(), tonight);
     }
-}
 
-fn run_set(status: &Status, back_date: Option<DateTime<Local>>, config: &Config) {
-    let is_back = status.keyword == "back";
+    #[test]
+    fn back_dates_add_the_date_once_a_weekday_would_be_ambiguous() {
+        let config = config();
+        let monday = date(2026, 3, 9);
+        let back = |day: u32| format_back_date(at(&config, date(2026, 3, day), 9, 0), monday, &config);
+        assert_eq!(back(15), "Back Sunday.");
+        assert_eq!(back(16), "Back Monday 3/16.");
+        assert_eq!(back(17), "Back 3/17.");
+        assert_eq!(format_back_date(at(&config, monday, 15, 0), monday, &config), "Back 3pm.");
+        assert_eq!(
+            format_back_date_with_time(at(&config, date(2026, 3, 16), 13, 30), monday, &config),
+            "Back Monday 3/16 1:30pm."
+        );
+        // A year or more out shows the year
+        assert_eq!(back_day_label(date(2027, 3, 10), monday), "3/10/2027");
+    }
 
-    // Slack (always runs — "back" clears DND then sets catching-up status)
-    if is_back
-        && let Ok(token) = std::env::var("SLACK_PAT")
-        && let Err(e) = end_slack_dnd(&token)
-    {
-        eprintln!("  Slack   \u{2717} ending DND: {e}");
+    #[test]
+    fn times_follow_the_configured_format() {
+        let h12 = config();
+        let h24 = Config { time_format: TimeFormat::H24, ..config() };
+        let cases = [(0, 0, "12am", "00:00"), (12, 0, "12pm", "12:00"), (13, 30, "1:30pm", "13:30"), (9, 5, "9:05am", "09:05")];
+        for (hour, minute, twelve, twenty_four) in cases {
+            assert_eq!(format_naive_time(time(hour, minute), &h12), twelve);
+            assert_eq!(format_naive_time(time(hour, minute), &h24), twenty_four);
+        }
     }
-    let show_back_in_text = matches!(status.keyword, "vacation" | "sick" | "away");
-    match set_slack_status(status, back_date, show_back_in_text) {
-        Ok(()) => {
-            let text = match (back_date, show_back_in_text) {
-                (Some(dt), true) => format!("{}. {}", status.slack_text, format_back_date_with_time(dt)),
-                _ => status.slack_text.to_string(),
-            };
-            let dnd_detail = match (status.slack_dnd, back_date) {
-                (true, Some(dt)) => format!(" (DND until {})", format_time(dt)),
-                (true, None) => " (DND on)".to_string(),
-                _ => String::new(),
-            };
-            let dnd_cleared = if is_back { " (DND off)" } else { "" };
-            println!("  Slack   \u{2713} {} {}{}{}", text, status.slack_emoji, dnd_detail, dnd_cleared);
-        }
-        Err(e) => eprintln!("  Slack   \u{2717} {e}"),
-    }
-
-    // GitHub — set busy, clear busy (for "back"), or no change
-    if is_back {
-        match clear_github_status() {
-            Ok(()) => println!("  GitHub  \u{2713} Cleared"),
-            Err(e) => eprintln!("  GitHub  \u{2717} {e}"),
-        }
-    } else if status.github_busy {
-        match set_github_status(status, back_date, config.github_org_id.as_deref()) {
-            Ok(()) => {
-                let org = if config.github_org_id.is_some() {
-                    " (Planning Center only)"
-                } else {
-                    ""
-                };
-                println!("  GitHub  \u{2713} Limited availability{org}");
-            }
-            Err(e) => eprintln!("  GitHub  \u{2717} {e}"),
+
+    #[test]
+    fn plain_eod_ends_at_the_next_workday_back_hour() {
+        let config = config();
+        let opts = opts();
+        let eod = find_status(&config, "eod").unwrap();
+        let back = |today| default_eod_back(eod, today, &config, &opts).unwrap();
+        assert_eq!(back(date(2026, 3, 9)), at(&config, date(2026, 3, 10), DEFAULT_BACK_HOUR, 0));
+        // Friday and the weekend roll to Monday
+        assert_eq!(back(date(2026, 3, 13)), at(&config, date(2026, 3, 16), DEFAULT_BACK_HOUR, 0));
+        assert_eq!(back(date(2026, 3, 14)), at(&config, date(2026, 3, 16), DEFAULT_BACK_HOUR, 0));
+
+        let config = Config { default_back_hour: Some(9), ..config };
+        let opts = RunOptions { holiday_dates: vec![date(2026, 3, 16)], ..opts };
+        let eod = find_status(&config, "eod").unwrap();
+        let back = default_eod_back(eod, date(2026, 3, 13), &config, &opts).unwrap();
+        assert_eq!(back, at(&config, date(2026, 3, 17), 9, 0));
+    }
+
+    #[test]
+    fn todays_day_name_with_a_time_ahead_means_today() {
+        let config = config();
+        let opts = opts();
+        let friday = date(2026, 3, 13);
+        let next_friday = date(2026, 3, 20);
+        let morning = at(&config, friday, 10, 0);
+        let evening = at(&config, friday, 18, 0);
+        let back = |now| parse_back_date("friday", Some("5pm"), None, now, &config, &opts).unwrap().0;
+        assert_eq!(back(morning), at(&config, friday, 17, 0));
+        assert_eq!(back(evening), at(&config, next_friday, 17, 0));
+        // A bare day name that is today is next week, and says so
+        let (back, note) = parse_back_date("friday", None, None, morning, &config, &opts).unwrap();
+        assert_eq!(back, at(&config, next_friday, DEFAULT_BACK_HOUR, 0));
+        assert_eq!(note.as_deref(), Some("Friday is today, using next week's 3/20"));
+        assert_eq!(parse_back_date("friday", Some("5pm"), None, morning, &config, &opts).unwrap().1, None);
+    }
+
+    #[test]
+    fn weeks_from_another_date() {
+        let config = config();
+        let monday = date(2026, 3, 9);
+        assert_eq!(parse_date("a week from friday", monday, &config).unwrap(), date(2026, 3, 20));
+        assert_eq!(parse_date("two weeks from friday", monday, &config).unwrap(), date(2026, 3, 27));
+        assert_eq!(parse_date("2 weeks from 3/10", monday, &config).unwrap(), date(2026, 3, 24));
+        assert_eq!(parse_date("friday+1w", monday, &config).unwrap(), date(2026, 3, 20));
+        assert_eq!(parse_date("friday+1w2d", monday, &config).unwrap(), date(2026, 3, 22));
+        // The base is the next one, even when that's tomorrow
+        let thursday = date(2026, 3, 12);
+        assert_eq!(parse_date("a week from friday", thursday, &config).unwrap(), date(2026, 3, 20));
+
+        for input in ["lots of weeks from friday", "0 weeks from friday", "friday+soon"] {
+            let err = parse_date(input, monday, &config).unwrap_err().to_string();
+            assert!(err.contains("Examples: a week from friday"), "{input}: {err}");
         }
-    } else {
-        println!("  GitHub  - No change");
     }
 
-    // Asana (no API for setting OOO — remind when relevant)
-    if status.keyword == "vacation" || status.keyword == "away" || status.keyword == "sick" {
-        if asana_ooo_summary(config).is_none() {
-            println!("  Asana   ! Set Out of Office manually: Profile (icon) > Set out of office");
-        } else {
-            println!("  Asana   \u{2713} Out of Office already set");
+    #[test]
+    fn natural_dates_loosen_phrases_only_when_on() {
+        let opts = opts();
+        let monday = date(2026, 3, 9);
+        let off = config();
+        let on = Config { natural_dates: true, ..config() };
+        let now = at(&on, monday, 10, 0);
+        assert!(parse_back_range("on dec 23rd", None, None, now, &off, &opts).is_err());
+        assert!(parse_back_range("friday at 3pm", None, None, now, &off, &opts).is_err());
+
+        let back = |input| parse_back_range(input, None, None, now, &on, &opts).unwrap().1;
+        assert_eq!(back("on dec 23rd"), at(&on, date(2026, 12, 23), DEFAULT_BACK_HOUR, 0));
+        assert_eq!(back("friday at 3pm"), at(&on, date(2026, 3, 13), 15, 0));
+        assert_eq!(back("monday, march 16th"), at(&on, date(2026, 3, 16), DEFAULT_BACK_HOUR, 0));
+        // What already parses never goes through the looser reading
+        assert_eq!(back("3/10"), at(&on, date(2026, 3, 10), DEFAULT_BACK_HOUR, 0));
+        assert_eq!(naturalize_date("3/10", &on), None);
+        // The looser reading is passed back to show the user
+        let note = parse_back_range("on dec 23rd", None, None, now, &on, &opts).unwrap().2;
+        assert_eq!(note.as_deref(), Some("interpreted 'on dec 23rd' as Wed 12/23 7am"));
+        assert_eq!(parse_back_range("3/10", None, None, now, &on, &opts).unwrap().2, None);
+    }
+
+    #[test]
+    fn natural_dates_reject_a_weekday_that_does_not_match() {
+        let opts = opts();
+        let on = Config { natural_dates: true, ..config() };
+        let now = at(&on, date(2026, 3, 9), 10, 0);
+        let err = parse_back_range("wednesday march 16", None, None, now, &on, &opts).unwrap_err().to_string();
+        assert!(err.contains("March 16 is a Monday"), "{err}");
+        assert!(parse_back_range("wed, march 18th at 3pm", None, None, now, &on, &opts).is_ok());
+    }
+
+    #[test]
+    fn ambiguous_hours_before_and_after_the_current_hour() {
+        let config = config();
+        let opts = opts();
+        let monday = date(2026, 3, 9);
+        let tuesday = date(2026, 3, 10);
+        let back = |input, hour| {
+            let now = at(&config, monday, hour, 0);
+            parse_back_date("today", Some(input), None, now, &config, &opts).map(|(back, _)| back)
+        };
+        // Before the hour has come around in the morning, it's still ahead
+        assert_eq!(back("3", 2).unwrap(), at(&config, monday, 3, 0));
+        assert_eq!(back("1", 0).unwrap(), at(&config, monday, 1, 0));
+        // After it has passed, 1-6 read as pm
+        assert_eq!(back("3", 4).unwrap(), at(&config, monday, 15, 0));
+        assert_eq!(back("1", 10).unwrap(), at(&config, monday, 13, 0));
+        // 11 is never read as pm: 11am ahead is kept, 11am passed is an error
+        assert_eq!(back("11", 10).unwrap(), at(&config, monday, 11, 0));
+        assert!(back("11", 12).is_err());
+        // With the time past in the afternoon too, there's nothing left today
+        assert!(back("3", 16).is_err());
+        assert_eq!(
+            parse_until("3", at(&config, monday, 16, 0), &config, &opts).unwrap(),
+            at(&config, tuesday, 3, 0)
+        );
+    }
+
+    #[test]
+    fn assume_pm_for_ambiguous_is_another_name_for_infer_pm() {
+        let config: Config = toml::from_str("assume_pm_for_ambiguous = false").unwrap();
+        assert_eq!(config.infer_pm, Some(false));
+        let config: Config = toml::from_str("infer_pm = true").unwrap();
+        assert_eq!(config.infer_pm, Some(true));
+    }
+
+    #[test]
+    fn plain_lunch_dnd_ends_at_the_return_not_a_day_later() {
+        let config = config();
+        let opts = opts();
+        for minute in 0..60 {
+            let now = at(&config, date(2026, 3, 9), 12, minute);
+            let back = parse_lunch_back_time(None, now, &config, &opts).unwrap();
+            let minutes = dnd_minutes(Some(back), now.with_timezone(&Utc));
+            assert!((60..=75).contains(&minutes), "{minutes} minutes at 12:{minute:02}");
         }
-    } else if is_back {
-        if asana_ooo_summary(config).is_some() {
-            println!("  Asana   ! Clear Out of Office manually: Profile (icon) > Set out of office");
-        } else {
-            println!("  Asana   - No change");
+        assert_eq!(dnd_minutes(None, Utc::now()), 1440);
+    }
+
+    #[test]
+    fn config_can_turn_github_busy_on_or_off_for_a_built_in() {
+        let opts = opts();
+        let mut config: Config = toml::from_str(
+            "github_token = \"test\"\n[statuses.sick]\ngithub_busy = true\n[statuses.vacation]\ngithub_busy = false\n",
+        )
+        .unwrap();
+        config.merge_status_tables();
+
+        let sick = find_status(&config, "sick").unwrap();
+        assert!(sick.github_busy);
+        assert_eq!(sick.slack_text, "Out sick");
+        let reports = github_set_report(sick, None, None, &config, &opts, true);
+        assert_eq!(reports[0].detail, "Limited availability (github_busy from config)");
+
+        let vacation = find_status(&config, "vacation").unwrap();
+        assert!(!vacation.github_busy);
+        let reports = github_set_report(vacation, None, None, &config, &opts, true);
+        assert_eq!(reports[0].detail, "No change (github_busy from config)");
+
+        // Built-ins the config leaves alone don't mention it
+        let away = find_status(&config, "away").unwrap();
+        assert_eq!(github_set_report(away, None, None, &config, &opts, true)[0].detail, "Limited availability");
+    }
+
+    #[test]
+    fn github_org_id_takes_a_single_id() {
+        let err = toml::from_str::<Config>("github_org_id = [\"O_a\", \"O_b\"]").err().unwrap().to_string();
+        assert!(err.contains("GitHub keeps one status per user"), "{err}");
+
+        let config: Config = toml::from_str("github_token = \"test\"\ngithub_org_id = \"O_a\"").unwrap();
+        let away = find_status(&config, "away").unwrap();
+        let reports = github_set_report(away, None, None, &config, &opts(), true);
+        assert_eq!(reports[0].detail, "Limited availability (org O_a only)");
+    }
+
+    #[test]
+    fn calendar_events_are_only_for_time_away() {
+        let opts = opts();
+        let config = Config { gcal_calendar_id: Some("primary".into()), ..config() };
+        let monday = date(2026, 3, 9);
+        let back = Some(at(&config, date(2026, 3, 13), DEFAULT_BACK_HOUR, 0));
+        let report = |keyword, back| {
+            calendar_set_report(find_status(&config, keyword).unwrap(), back, None, monday, &config, &opts, true)
+        };
+        for keyword in ["vacation", "away", "sick"] {
+            assert!(matches!(report(keyword, back)[0].outcome, Outcome::Ok), "{keyword}");
         }
-    } else {
-        println!("  Asana   - No change");
+        for keyword in ["lunch", "meet", "conference", "back"] {
+            assert_eq!(report(keyword, back)[0].detail, "No change", "{keyword}");
+        }
+        assert_eq!(report("vacation", None)[0].detail, "No back date, no event created");
+        // No calendar, no line in the report at all
+        let config = Config { gcal_calendar_id: None, ..config };
+        let vacation = find_status(&config, "vacation").unwrap();
+        assert!(calendar_set_report(vacation, back, None, monday, &config, &opts, true).is_empty());
     }
-}
 
-fn run_clear(config: &Config) {
-    match clear_slack_status() {
-        Ok(()) => println!("  Slack   \u{2713} Cleared (DND off)"),
-        Err(e) => eprintln!("  Slack   \u{2717} {e}"),
+    #[test]
+    fn calendar_events_start_today_or_at_a_later_first_day_out() {
+        let opts = opts();
+        let config = Config { gcal_calendar_id: Some("primary".into()), ..config() };
+        let vacation = find_status(&config, "vacation").unwrap();
+        let monday = date(2026, 3, 9);
+        let back = Some(at(&config, date(2026, 3, 16), DEFAULT_BACK_HOUR, 0));
+        let start = |leave_start| {
+            let reports = calendar_set_report(vacation, back, leave_start, monday, &config, &opts, true);
+            reports[0].fields["start"].as_str().unwrap().to_string()
+        };
+        assert_eq!(start(None), "2026-03-09");
+        assert_eq!(start(Some(date(2026, 3, 11))), "2026-03-11");
+        // A range that has already started is clamped to today
+        assert_eq!(start(Some(date(2026, 3, 2))), "2026-03-09");
     }
 
-    match clear_github_status() {
-        Ok(()) => println!("  GitHub  \u{2713} Cleared"),
-        Err(e) => eprintln!("  GitHub  \u{2717} {e}"),
+    #[test]
+    fn status_text_placeholders_expand_to_the_back_date() {
+        let config = config();
+        let (monday, friday) = (date(2026, 3, 9), date(2026, 3, 13));
+        let morning = at(&config, friday, DEFAULT_BACK_HOUR, 0);
+        let afternoon = at(&config, friday, 13, 30);
+        let expand = |text, dt| fill_back_placeholders(text, dt, monday, &config);
+        assert_eq!(expand("OOO \u{2014} back {back}", morning), "OOO \u{2014} back Friday");
+        assert_eq!(expand("OOO \u{2014} back {back}", afternoon), "OOO \u{2014} back Friday 1:30pm");
+        assert_eq!(expand("Lunchin' until {time}", afternoon), "Lunchin' until 1:30pm");
+        // Back later today is just the time
+        assert_eq!(expand("Out until {back}", at(&config, monday, 15, 0)), "Out until 3pm");
+        assert_eq!(expand_status_text("Out sick", Some(morning), &config), None);
     }
 
-    if asana_ooo_summary(config).is_some() {
-        println!("  Asana   ! Clear Out of Office manually: Profile (icon) > Set out of office");
-    } else {
-        println!("  Asana   - No change");
+    #[test]
+    fn empty_placeholders_take_their_lead_in_with_them() {
+        let config = config();
+        let expand = |text| expand_status_text(text, None, &config).unwrap();
+        assert_eq!(expand("OOO \u{2014} back {back}"), "OOO");
+        assert_eq!(expand("Lunchin' until {time}"), "Lunchin'");
+        assert_eq!(expand("Out until {back}, ping me"), "Out, ping me");
+        assert_eq!(expand("Away {back}. Email me"), "Away. Email me");
+        assert_eq!(expand("Back {back}. Email me"), "Email me");
+        assert_eq!(expand("{back}"), "");
+    }
+
+    #[test]
+    fn day_after_tomorrow() {
+        let config = config();
+        let opts = opts();
+        let monday = date(2026, 3, 9);
+        let wednesday = date(2026, 3, 11);
+        for input in ["day after tomorrow", "overmorrow", "Day  After   Tomorrow", "day-after-tomorrow"] {
+            assert_eq!(parse_date(input, monday, &config).unwrap(), wednesday, "{input}");
+        }
+        let now = at(&config, monday, 10, 0);
+        assert_eq!(
+            parse_back_date("day after tomorrow", Some("2pm"), None, now, &config, &opts).unwrap().0,
+            at(&config, wednesday, 14, 0)
+        );
+        assert_eq!(
+            parse_back_date("overmorrow", None, None, now, &config, &opts).unwrap().0,
+            at(&config, wednesday, DEFAULT_BACK_HOUR, 0)
+        );
+    }
+
+    #[test]
+    fn appointment_is_a_same_day_dnd_status() {
+        let config = config();
+        let opts = opts();
+        let appointment = find_status(&config, "appointment").unwrap();
+        assert_eq!(appointment.slack_text, "At an appointment");
+        assert_eq!(appointment.slack_emoji, ":hospital:");
+        assert!(appointment.slack_dnd);
+        assert!(!appointment.github_busy);
+
+        let monday = date(2026, 3, 9);
+        let now = at(&config, monday, 10, 0);
+        let (_, back, _) = parse_back_range("3pm", None, None, now, &config, &opts).unwrap();
+        assert_eq!(back, at(&config, monday, 15, 0));
+        // With no argument it's an hour, rounded like lunch
+        assert_eq!(default_lunch_back(now, 15, DEFAULT_APPOINTMENT_MINUTES), at(&config, monday, 11, 15));
+    }
+
+    #[test]
+    fn conference_name_comes_off_the_front_of_the_arguments() {
+        let config = config();
+        let opts = opts();
+        let take = |args: &[&str]| {
+            let mut cli = Cli::parse_from([&["st", "conference"], args].concat());
+            let name = take_conference_name(&mut cli, &config, &opts);
+            (name, cli.back_date, cli.back_time)
+        };
+        let some = |s: &str| Some(s.to_string());
+        assert_eq!(take(&["RustConf", "friday"]), (some("RustConf"), some("friday"), None));
+        assert_eq!(take(&["Rust Belt Rust", "friday", "9am"]), (some("Rust Belt Rust"), some("friday"), some("9am")));
+        assert_eq!(take(&["RustConf", "3/10-3/12"]), (some("RustConf"), some("3/10-3/12"), None));
+        assert_eq!(take(&["RustConf"]), (some("RustConf"), None, None));
+        // A back date first means there's no name
+        assert_eq!(take(&["friday"]), (None, some("friday"), None));
+        assert_eq!(take(&["friday", "5pm"]), (None, some("friday"), some("5pm")));
+        assert_eq!(take(&[]), (None, None, None));
+    }
+
+    #[test]
+    fn snooze_lengths() {
+        assert_eq!(parse_snooze(Some("45")).unwrap(), Some(45));
+        assert_eq!(parse_snooze(Some("1h30m")).unwrap(), Some(90));
+        assert_eq!(parse_snooze(Some("off")).unwrap(), None);
+        assert!(parse_snooze(Some("0")).is_err());
+        let err = parse_snooze(Some("9223372036854775807")).unwrap_err().to_string();
+        assert_eq!(err, "Snooze length is too long: 9223372036854775807");
     }
 }